@@ -1,18 +1,24 @@
 use bevy_math::UVec2;
 use bitmap_font::bfn::{BoundingBox, Font, Glyph};
 use image::{
-    codecs::png::PngEncoder, ColorType, DynamicImage, GenericImage, ImageEncoder, Rgba, RgbaImage,
+    codecs::png::PngEncoder, ColorType, DynamicImage, GenericImage, ImageEncoder, ImageError, Rgba,
+    RgbaImage,
 };
+use quick_xml::DeError;
 use std::{
     collections::HashMap,
-    fs::{self, File},
-    io::Cursor,
-    path::Path,
+    fs,
+    io::{self, Cursor},
+    path::{Path, PathBuf},
 };
+use thiserror::Error;
 
 use crunch::{Item, PackedItem, Rect, Rotation};
 
-use crate::{bitmap_fonts::font_data::FontData, utils::list_files_in_folder};
+use crate::{
+    bitmap_fonts::font_data::FontData,
+    utils::{list_files_in_folder, write_atomically},
+};
 
 mod font_data;
 
@@ -22,37 +28,77 @@ pub struct TextureAtlasEntry<T> {
     pub texture: RgbaImage,
 }
 
-pub fn create_bitmap_font(source_directory: &Path, output_filename: &str, atlas_size: UVec2) {
+#[derive(Debug, Error)]
+pub enum BitmapFontError {
+    #[error("failed to read {path}: {source}")]
+    Read { path: PathBuf, source: io::Error },
+    #[error("failed to parse FontData.xml: {0}")]
+    FontData(#[from] DeError),
+    #[error("failed to open {path}: {source}")]
+    Texture { path: PathBuf, source: ImageError },
+    #[error("the {glyph_count} glyphs don't fit in a {width}x{height} atlas")]
+    Pack {
+        glyph_count: usize,
+        width: u32,
+        height: u32,
+    },
+    #[error("failed to encode the atlas: {0}")]
+    Encode(#[source] ImageError),
+    #[error("failed to write the font: {0}")]
+    Write(#[source] io::Error),
+}
+
+pub fn create_bitmap_font(
+    source_directory: &Path,
+    output_filename: &str,
+    atlas_size: UVec2,
+) -> Result<(), BitmapFontError> {
     println!("Start packing font {}", output_filename);
-    let font_texture_files = list_files_in_folder(source_directory, Some("png"))
-        .unwrap_or_else(|_| panic!("Unable to list texture files in {:?}", source_directory));
+    let font_texture_files =
+        list_files_in_folder(source_directory, Some("png")).map_err(|source| {
+            BitmapFontError::Read {
+                path: source_directory.to_path_buf(),
+                source,
+            }
+        })?;
 
     let font_data_path = source_directory.join("FontData.xml");
-    let font_data_content = fs::read(font_data_path).expect("Failed to read FontData.xml");
-    let font_data =
-        FontData::parse_from_xml(&font_data_content).expect("Failed to parse FontData.xml");
+    let font_data_content = fs::read(&font_data_path).map_err(|source| BitmapFontError::Read {
+        path: font_data_path,
+        source,
+    })?;
+    let font_data = FontData::parse_from_xml(&font_data_content)?;
 
     let output_path = Path::new(output_filename);
-    fs::create_dir_all(output_path.parent().unwrap().to_str().unwrap()).unwrap();
-
-    let entries: Vec<_> = font_texture_files
-        .iter()
-        .filter_map(|file| {
-            let texture = image::open(file).unwrap().to_rgba8();
-            let (w, h) = (texture.width() as usize, texture.height() as usize);
-
-            let path = Path::new(file);
-            let file_stem = path.file_stem().and_then(|n| n.to_str())?;
-            let id = u32::from_str_radix(file_stem, 16).ok()?;
-
-            Some(Item::new(
-                TextureAtlasEntry { texture, id },
-                w,
-                h,
-                Rotation::None,
-            ))
-        })
-        .collect();
+    fs::create_dir_all(output_path.parent().unwrap()).map_err(BitmapFontError::Write)?;
+
+    let mut entries = vec![];
+    for file in font_texture_files.iter() {
+        // Only the glyph textures are named after their code point
+        let path = Path::new(file);
+        let Some(id) = path
+            .file_stem()
+            .and_then(|n| n.to_str())
+            .and_then(|file_stem| u32::from_str_radix(file_stem, 16).ok())
+        else {
+            continue;
+        };
+
+        let texture = image::open(path)
+            .map_err(|source| BitmapFontError::Texture {
+                path: path.to_path_buf(),
+                source,
+            })?
+            .to_rgba8();
+        let (w, h) = (texture.width() as usize, texture.height() as usize);
+
+        entries.push(Item::new(
+            TextureAtlasEntry { texture, id },
+            w,
+            h,
+            Rotation::None,
+        ));
+    }
 
     println!("packing {} glyph font images...", entries.len());
 
@@ -66,85 +112,89 @@ pub fn create_bitmap_font(source_directory: &Path, output_filename: &str, atlas_
     let dest = Rect::new(0, 0, atlas_size.x as usize, atlas_size.y as usize);
     println!("Packing font into {:?}", dest);
 
-    match crunch::pack(dest, entries) {
-        Ok(all_packed) => {
-            // Create a target atlas image to draw the packed images onto
-            let mut atlas = RgbaImage::from_pixel(dest.w as u32, dest.h as u32, Rgba([0, 0, 0, 0]));
-
-            let mut glyphs: HashMap<u32, Glyph> = HashMap::with_capacity(all_packed.len());
-
-            // Copy all the packed images onto the target atlas
-            for PackedItem { data, rect } in all_packed.iter() {
-                atlas
-                    .copy_from(&data.texture, rect.x as u32, rect.y as u32)
-                    .unwrap();
-
-                if font_data.colorless.glyphs.contains(&data.id) {
-                    println!("{:X} is colorless", data.id);
-                }
-
-                glyphs.insert(
-                    data.id,
-                    Glyph {
-                        code_point: data.id,
-                        bounds: BoundingBox {
-                            width: rect.w,
-                            height: rect.h,
-                            x: rect.x,
-                            y: rect.y,
-                        },
-                        colorless: font_data.colorless.glyphs.contains(&data.id),
-                    },
-                );
-            }
+    let glyph_count = entries.len();
+    let all_packed = crunch::pack(dest, entries).map_err(|_| BitmapFontError::Pack {
+        glyph_count,
+        width: atlas_size.x,
+        height: atlas_size.y,
+    })?;
 
-            glyphs.insert(
-                ' ' as u32,
-                Glyph {
-                    code_point: ' ' as u32,
-                    bounds: BoundingBox {
-                        width: 4,
-                        height: 12,
-                        x: 0,
-                        y: 0,
-                    },
-                    colorless: false,
-                },
-            );
-
-            println!("exporting `{}`...", output_filename);
-
-            // Export the packed atlas
-            atlas.save(format!("{output_filename}-debug.png")).unwrap();
-
-            let mut texture_bytes: Vec<u8> = Vec::new();
-            let mut cursor = Cursor::new(&mut texture_bytes);
-            let encoder = PngEncoder::new(&mut cursor);
-            encoder
-                .write_image(
-                    atlas.as_raw(),
-                    atlas.width(),
-                    atlas.height(),
-                    ColorType::Rgba8.into(),
-                )
-                .expect("Failed to compress atlas image");
-
-            let font_sheet_data = Font {
-                size: (dest.w, dest.h),
-                name: font_name.to_string(),
-                glyph_count: glyphs.len(),
-                char_space: font_data.char_space,
-                char_height: font_data.char_height,
-                space_width: font_data.space_width,
-                line_space: font_data.line_space,
-                glyphs,
-                texture: texture_bytes,
-            };
-            let mut font_sheet_file = File::create(output_filename).unwrap();
-            let _ = font_sheet_data.save(&mut font_sheet_file);
-        }
-        Err(_) => {
-            panic!("failed to pack images");
+    // Create a target atlas image to draw the packed images onto
+    let mut atlas = RgbaImage::from_pixel(dest.w as u32, dest.h as u32, Rgba([0, 0, 0, 0]));
+
+    let mut glyphs: HashMap<u32, Glyph> = HashMap::with_capacity(all_packed.len());
+
+    // Copy all the packed images onto the target atlas
+    for PackedItem { data, rect } in all_packed.iter() {
+        atlas
+            .copy_from(&data.texture, rect.x as u32, rect.y as u32)
+            .unwrap();
+
+        if font_data.colorless.glyphs.contains(&data.id) {
+            println!("{:X} is colorless", data.id);
         }
+
+        glyphs.insert(
+            data.id,
+            Glyph {
+                code_point: data.id,
+                bounds: BoundingBox {
+                    width: rect.w,
+                    height: rect.h,
+                    x: rect.x,
+                    y: rect.y,
+                },
+                colorless: font_data.colorless.glyphs.contains(&data.id),
+            },
+        );
     }
+
+    glyphs.insert(
+        ' ' as u32,
+        Glyph {
+            code_point: ' ' as u32,
+            bounds: BoundingBox {
+                width: 4,
+                height: 12,
+                x: 0,
+                y: 0,
+            },
+            colorless: false,
+        },
+    );
+
+    println!("exporting `{}`...", output_filename);
+
+    // Export the packed atlas
+    atlas
+        .save(format!("{output_filename}-debug.png"))
+        .map_err(BitmapFontError::Encode)?;
+
+    let mut texture_bytes: Vec<u8> = Vec::new();
+    let mut cursor = Cursor::new(&mut texture_bytes);
+    let encoder = PngEncoder::new(&mut cursor);
+    encoder
+        .write_image(
+            atlas.as_raw(),
+            atlas.width(),
+            atlas.height(),
+            ColorType::Rgba8.into(),
+        )
+        .map_err(BitmapFontError::Encode)?;
+
+    let font_sheet_data = Font {
+        size: (dest.w, dest.h),
+        name: font_name.to_string(),
+        glyph_count: glyphs.len(),
+        char_space: font_data.char_space,
+        char_height: font_data.char_height,
+        space_width: font_data.space_width,
+        line_space: font_data.line_space,
+        glyphs,
+        texture: texture_bytes,
+    };
+    write_atomically(output_path, |font_sheet_file| {
+        font_sheet_data.save(font_sheet_file)
+    })
+    .map_err(BitmapFontError::Write)
 }
@@ -76,9 +76,8 @@ impl AnimInfo<'_> {
                 let reference = self.anims.anim.get(&copy_of.copy_of).unwrap();
                 match reference {
                     Anim::Value(reference_value) => reference_value,
-                    Anim::CopyOf(_) => {
-                        panic!("Can't copy {:?} for {:?}", copy_of.name, copy_of.copy_of)
-                    }
+                    // Checked when parsed
+                    Anim::CopyOf(_) => unreachable!(),
                 }
             }
         };
@@ -189,7 +188,7 @@ impl std::convert::TryFrom<AnimRaw> for Anim {
 }
 
 impl std::convert::TryFrom<AnimsRaw> for Anims {
-    type Error = &'static str;
+    type Error = String;
     fn try_from(anims_raw: AnimsRaw) -> Result<Self, Self::Error> {
         let anim_map = anims_raw
             .anim
@@ -204,6 +203,19 @@ impl std::convert::TryFrom<AnimsRaw> for Anims {
             })
             .collect::<HashMap<_, _>>();
 
+        // A copy must point to an animation with its own frames
+        for anim in anim_map.values() {
+            let Anim::CopyOf(copy_of) = anim else {
+                continue;
+            };
+            if !matches!(anim_map.get(&copy_of.copy_of), Some(Anim::Value(_))) {
+                return Err(format!(
+                    "Can't copy {:?} for {:?}",
+                    copy_of.name, copy_of.copy_of
+                ));
+            }
+        }
+
         Ok(Anims { anim: anim_map })
     }
 }
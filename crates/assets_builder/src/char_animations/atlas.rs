@@ -0,0 +1,260 @@
+use std::{cmp::Reverse, collections::HashMap};
+
+use bevy_math::{URect, UVec2};
+use char_animation::anim_key::AnimKey;
+use crunch::{Item, PackedItem, Rect, Rotation};
+use image::{GenericImage, GenericImageView, Rgba, RgbaImage};
+
+use super::CharAnimationError;
+
+/// The biggest side of an atlas page
+pub const PAGE_MAX_SIZE: u32 = 2048;
+
+/// The frames of an animation sheet, all of them end up on the same page
+pub struct SheetFrames<'a> {
+    pub anim: AnimKey,
+    pub sheet: &'a RgbaImage,
+    pub frames: Vec<URect>,
+}
+
+/// Where the frames of a sheet were packed
+pub struct PackedSheet {
+    pub page: usize,
+    pub frames: Vec<URect>,
+}
+
+/// The sheets of a page and where their frames, identified by their sheet and frame index, are
+#[derive(Default)]
+struct PageLayout {
+    sheets: Vec<usize>,
+    frames: Vec<PackedItem<(usize, usize)>>,
+}
+
+pub struct Atlas {
+    pub pages: Vec<RgbaImage>,
+    pub sheets: HashMap<AnimKey, PackedSheet>,
+}
+
+/// Pack the frames of every sheet into as few pages as possible, the tallest sheets first, a
+/// sheet goes to the last page unless its frames no longer fit in it
+pub fn pack_sheets(sheets: &[SheetFrames]) -> Result<Atlas, CharAnimationError> {
+    let mut order = (0..sheets.len()).collect::<Vec<_>>();
+    order.sort_by_key(|&index| {
+        let sheet = &sheets[index];
+        let height = sheet.frames.iter().map(URect::height).max();
+        (Reverse(height), Reverse(sheet_area(sheet)))
+    });
+
+    let mut page_layouts: Vec<PageLayout> = vec![];
+    for index in order {
+        if let Some(current) = page_layouts.last_mut() {
+            if let Some(frames) = pack_page(sheets, &current.sheets, index) {
+                current.sheets.push(index);
+                current.frames = frames;
+                continue;
+            }
+        }
+
+        let frames = pack_page(sheets, &[], index).ok_or(CharAnimationError::Pack {
+            anim: sheets[index].anim,
+            page_size: PAGE_MAX_SIZE,
+        })?;
+        page_layouts.push(PageLayout {
+            sheets: vec![index],
+            frames,
+        });
+    }
+
+    let mut atlas = Atlas {
+        pages: Vec::with_capacity(page_layouts.len()),
+        sheets: HashMap::with_capacity(sheets.len()),
+    };
+    for (page, layout) in page_layouts.into_iter().enumerate() {
+        let size = page_size(&layout.frames);
+        let mut image = RgbaImage::from_pixel(size.x, size.y, Rgba([0, 0, 0, 0]));
+        for PackedItem {
+            data: (sheet, frame),
+            rect,
+        } in layout.frames
+        {
+            let source = sheets[sheet].frames[frame];
+            let target = URect::new(
+                rect.x as u32,
+                rect.y as u32,
+                (rect.x + rect.w) as u32,
+                (rect.y + rect.h) as u32,
+            );
+            let frame_image = sheets[sheet]
+                .sheet
+                .view(source.min.x, source.min.y, source.width(), source.height())
+                .to_image();
+            image
+                .copy_from(&frame_image, target.min.x, target.min.y)
+                .expect("The packed frame is inside its page");
+
+            atlas
+                .sheets
+                .entry(sheets[sheet].anim)
+                .or_insert_with(|| PackedSheet {
+                    page,
+                    frames: vec![URect::default(); sheets[sheet].frames.len()],
+                })
+                .frames[frame] = target;
+        }
+        atlas.pages.push(image);
+    }
+
+    Ok(atlas)
+}
+
+/// Pack the frames of the page sheets with the ones of the new sheet, `None` when they don't
+/// all fit in a page
+fn pack_page(
+    sheets: &[SheetFrames],
+    page_sheets: &[usize],
+    new_sheet: usize,
+) -> Option<Vec<PackedItem<(usize, usize)>>> {
+    let items = page_sheets.iter().chain([&new_sheet]).flat_map(|&sheet| {
+        sheets[sheet]
+            .frames
+            .iter()
+            .enumerate()
+            .map(move |(frame, rect)| {
+                Item::new(
+                    (sheet, frame),
+                    rect.width() as usize,
+                    rect.height() as usize,
+                    Rotation::None,
+                )
+            })
+    });
+
+    let page = Rect::new(0, 0, PAGE_MAX_SIZE as usize, PAGE_MAX_SIZE as usize);
+    crunch::pack(page, items).ok()
+}
+
+/// The smallest power of two size the packed frames fit in
+fn page_size(frames: &[PackedItem<(usize, usize)>]) -> UVec2 {
+    let size = frames
+        .iter()
+        .fold(UVec2::ONE, |size, PackedItem { rect, .. }| {
+            size.max(UVec2::new(
+                (rect.x + rect.w) as u32,
+                (rect.y + rect.h) as u32,
+            ))
+        });
+    UVec2::new(size.x.next_power_of_two(), size.y.next_power_of_two())
+}
+
+fn sheet_area(sheet: &SheetFrames) -> u32 {
+    sheet
+        .frames
+        .iter()
+        .map(|rect| rect.width() * rect.height())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::{URect, UVec2};
+    use char_animation::anim_key::AnimKey;
+    use image::{GenericImageView, Rgba, RgbaImage};
+
+    use crate::char_animations::CharAnimationError;
+
+    use super::{pack_sheets, SheetFrames, PAGE_MAX_SIZE};
+
+    /// A sheet of `columns` x `rows` frames, every pixel tells its frame apart
+    fn sheet(frame_size: UVec2, columns: u32, rows: u32) -> (RgbaImage, Vec<URect>) {
+        let size = frame_size * UVec2::new(columns, rows);
+        let texture = RgbaImage::from_fn(size.x, size.y, |x, y| {
+            let frame = (y / frame_size.y) * columns + x / frame_size.x;
+            Rgba([
+                frame as u8,
+                (x % frame_size.x) as u8,
+                (y % frame_size.y) as u8,
+                255,
+            ])
+        });
+        let frames = (0..rows)
+            .flat_map(|row| {
+                (0..columns).map(move |column| {
+                    let min = UVec2::new(column, row) * frame_size;
+                    URect::from_corners(min, min + frame_size)
+                })
+            })
+            .collect();
+        (texture, frames)
+    }
+
+    #[test]
+    fn packs_every_frame_on_its_page_without_overlaps() {
+        let walk = sheet(UVec2::new(40, 40), 6, 8);
+        let attack = sheet(UVec2::new(64, 72), 10, 8);
+        let idle = sheet(UVec2::new(PAGE_MAX_SIZE / 2, PAGE_MAX_SIZE / 2), 2, 2);
+        let sheets = [
+            (AnimKey::Walk, &walk),
+            (AnimKey::Attack, &attack),
+            (AnimKey::Idle, &idle),
+        ]
+        .map(|(anim, (texture, frames))| SheetFrames {
+            anim,
+            sheet: texture,
+            frames: frames.clone(),
+        });
+
+        let atlas = pack_sheets(&sheets).unwrap();
+
+        assert_eq!(atlas.pages.len(), 2, "The idle frames fill a page");
+        let mut packed = vec![];
+        for sheet in sheets.iter() {
+            let packed_sheet = &atlas.sheets[&sheet.anim];
+            let page = &atlas.pages[packed_sheet.page];
+            for (source, target) in sheet.frames.iter().zip(&packed_sheet.frames) {
+                assert_eq!(source.size(), target.size());
+                assert!(target.max.x <= page.width() && target.max.y <= page.height());
+                let expected = sheet
+                    .sheet
+                    .view(source.min.x, source.min.y, source.width(), source.height())
+                    .to_image();
+                let found = page
+                    .view(target.min.x, target.min.y, target.width(), target.height())
+                    .to_image();
+                assert!(expected == found, "{:?} frame {source:?}", sheet.anim);
+                packed.push((packed_sheet.page, *target));
+            }
+        }
+
+        for (index, (page, rect)) in packed.iter().enumerate() {
+            for (other_page, other_rect) in packed[index + 1..].iter() {
+                assert!(
+                    page != other_page || rect.intersect(*other_rect).is_empty(),
+                    "{rect:?} overlaps {other_rect:?} on page {page}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_a_sheet_larger_than_a_page() {
+        let (texture, frames) = sheet(UVec2::new(PAGE_MAX_SIZE + 1, 8), 1, 1);
+        let sheets = [SheetFrames {
+            anim: AnimKey::Walk,
+            sheet: &texture,
+            frames,
+        }];
+
+        let result = pack_sheets(&sheets);
+        assert!(
+            matches!(
+                result,
+                Err(CharAnimationError::Pack {
+                    anim: AnimKey::Walk,
+                    page_size: PAGE_MAX_SIZE
+                })
+            ),
+            "{:?}",
+            result.err()
+        );
+    }
+}
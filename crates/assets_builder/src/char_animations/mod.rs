@@ -1,40 +1,101 @@
 mod anim_data;
+mod atlas;
 
 use std::{
-    collections::HashMap,
-    fs::{self, File},
-    io::Cursor,
-    path::Path,
+    collections::{hash_map::Entry, HashMap},
+    fs, io,
+    path::{Path, PathBuf},
 };
 
 use ::char_animation::orientation::Orientation;
 use bevy_math::{IVec2, URect, UVec2, Vec2};
-use char_animation::file::{CharAnimationFile, CharAnimationFileEntry, CharAnimationOffsets};
-use image::{codecs::png::PngEncoder, ColorType, DynamicImage, ImageBuffer, ImageEncoder, Rgba};
+use char_animation::{
+    anim_key::AnimKey,
+    file::{CharAnimationFile, CharAnimationFileEntry, CharAnimationOffsets, FrameRect},
+};
+use image::{DynamicImage, ImageBuffer, ImageError, Rgba, RgbaImage};
+use quick_xml::DeError;
+use thiserror::Error;
+
+use crate::utils::{encode_png, write_atomically};
 
-use self::anim_data::{AnimData, AnimInfo};
+use self::{
+    anim_data::{AnimData, AnimInfo},
+    atlas::{pack_sheets, SheetFrames},
+};
 
-pub fn create_char_animation(source_directory: &Path, output_filename: &str) {
+#[derive(Debug, Error)]
+pub enum CharAnimationError {
+    #[error("failed to read {path}: {source}")]
+    Read { path: PathBuf, source: io::Error },
+    #[error("failed to parse AnimData.xml: {0}")]
+    AnimData(#[from] DeError),
+    #[error("failed to open {path}: {source}")]
+    Texture { path: PathBuf, source: ImageError },
+    #[error("{path} is {}x{}, smaller than the {}x{} sheet of AnimData.xml", found.x, found.y, expected.x, expected.y)]
+    TextureSize {
+        path: PathBuf,
+        expected: UVec2,
+        found: UVec2,
+    },
+    #[error("no shadow offset in frame {frame} of {anim:?} {orientation:?}")]
+    MissingShadowOffset {
+        anim: AnimKey,
+        orientation: Orientation,
+        frame: usize,
+    },
+    #[error("found {found} of the 4 offset parts in frame {frame} of {anim:?} {orientation:?}")]
+    MissingOffsets {
+        anim: AnimKey,
+        orientation: Orientation,
+        frame: usize,
+        found: usize,
+    },
+    #[error("the {anim:?} frames don't fit in a {page_size}x{page_size} atlas page")]
+    Pack { anim: AnimKey, page_size: u32 },
+    #[error("failed to compress atlas page {page}: {source}")]
+    Compress { page: usize, source: ImageError },
+    #[error("failed to write the char animation: {0}")]
+    Write(#[source] io::Error),
+}
+
+pub fn create_char_animation(
+    source_directory: &Path,
+    output_filename: &str,
+) -> Result<(), CharAnimationError> {
     println!("Creating {output_filename}");
     let output_path = Path::new(output_filename);
-    fs::create_dir_all(output_path.parent().unwrap()).unwrap();
+    fs::create_dir_all(output_path.parent().unwrap()).map_err(CharAnimationError::Write)?;
 
     let anim_data_path = source_directory.join("AnimData.xml");
-    let anim_data_content = fs::read(anim_data_path).expect("Failed to read AnimData.xml");
-    let anim_data =
-        AnimData::parse_from_xml(&anim_data_content).expect("Failed to parse AnimData.xml");
+    let anim_data_content =
+        fs::read(&anim_data_path).map_err(|source| CharAnimationError::Read {
+            path: anim_data_path,
+            source,
+        })?;
+    let anim_data = AnimData::parse_from_xml(&anim_data_content)?;
 
     let mut char_animation_entries = HashMap::new();
+    // The copies share the sheet of the animation they copy
+    let mut sheets: HashMap<AnimKey, TrimmedSheet> = HashMap::new();
 
     for (anim_key, _) in anim_data.anims.anim.iter() {
         let anim_info = anim_data.get(anim_key);
         let anim_key_str: &'static str = anim_info.value().name.into();
+        let sheet_size = anim_info.tile_size()
+            * UVec2::new(
+                anim_info.columns() as u32,
+                anim_info.orientations().count() as u32,
+            );
 
-        let offsets_texture_file = source_directory.join(format!("{anim_key_str}-Offsets.png"));
-        let offsets_texture = image::open(offsets_texture_file).unwrap().to_rgba8();
-
-        let shadow_texture_file = source_directory.join(format!("{anim_key_str}-Shadow.png"));
-        let shadow_texture = image::open(shadow_texture_file).unwrap().to_rgba8();
+        let offsets_texture = open_sheet(
+            &source_directory.join(format!("{anim_key_str}-Offsets.png")),
+            sheet_size,
+        )?;
+        let shadow_texture = open_sheet(
+            &source_directory.join(format!("{anim_key_str}-Shadow.png")),
+            sheet_size,
+        )?;
 
         let mut offsets = HashMap::new();
         let mut shadow_offsets = HashMap::new();
@@ -60,15 +121,37 @@ pub fn create_char_animation(source_directory: &Path, output_filename: &str) {
                 );
 
                 offsets.get_mut(&orientation).unwrap()[column] =
-                    extract_offsets(&anim_info, &offsets_texture, texture_rect);
+                    extract_offsets(&anim_info, &offsets_texture, texture_rect).map_err(
+                        |found| CharAnimationError::MissingOffsets {
+                            anim: *anim_key,
+                            orientation: orientation.clone(),
+                            frame: column,
+                            found,
+                        },
+                    )?;
 
                 shadow_offsets.get_mut(&orientation).unwrap()[column] =
-                    extract_shadow_offset(&anim_info, &shadow_texture, texture_rect);
+                    extract_shadow_offset(&anim_info, &shadow_texture, texture_rect).ok_or_else(
+                        || CharAnimationError::MissingShadowOffset {
+                            anim: *anim_key,
+                            orientation: orientation.clone(),
+                            frame: column,
+                        },
+                    )?;
             }
         }
 
-        let animation_texture_file = source_directory.join(format!("{anim_key_str}-Anim.png"));
-        let animation_texture = image::open(animation_texture_file).unwrap().to_rgba8();
+        let sheet_key = anim_info.value().name;
+        if let Entry::Vacant(sheet) = sheets.entry(sheet_key) {
+            let animation_texture = open_sheet(
+                &source_directory.join(format!("{anim_key_str}-Anim.png")),
+                sheet_size,
+            )?;
+            sheet.insert(TrimmedSheet::new(
+                animation_texture,
+                &sheet_frames(&anim_info),
+            ));
+        }
 
         let durations = anim_info
             .value()
@@ -78,22 +161,13 @@ pub fn create_char_animation(source_directory: &Path, output_filename: &str) {
             .map(|d| d.value)
             .collect::<Vec<_>>();
 
-        let mut animation_texture_bytes: Vec<u8> = Vec::new();
-        let mut cursor = Cursor::new(&mut animation_texture_bytes);
-        let encoder = PngEncoder::new(&mut cursor);
-        encoder
-            .write_image(
-                animation_texture.as_raw(),
-                animation_texture.width(),
-                animation_texture.height(),
-                ColorType::Rgba8.into(),
-            )
-            .expect("Failed to compress animation texture");
-
         let char_animation_entry = CharAnimationFileEntry {
-            texture: animation_texture_bytes,
+            // Filled once the sheets are packed
+            page: 0,
+            frames: vec![],
+            crop_offsets: vec![],
             index: anim_info.index(),
-            is_single_orientation: columns == 1,
+            is_single_orientation: anim_info.orientations().count() == 1,
             frame_width: anim_info.tile_size().x,
             frame_height: anim_info.tile_size().y,
             durations,
@@ -104,22 +178,164 @@ pub fn create_char_animation(source_directory: &Path, output_filename: &str) {
             offsets,
         };
 
-        char_animation_entries.insert(*anim_key, char_animation_entry);
+        char_animation_entries.insert(*anim_key, (sheet_key, char_animation_entry));
     }
 
-    let mut char_animation_file = File::create(output_filename).unwrap();
+    let sheet_frames = sheets
+        .iter()
+        .map(|(anim, sheet)| SheetFrames {
+            anim: *anim,
+            sheet: &sheet.texture,
+            frames: sheet.frames.clone(),
+        })
+        .collect::<Vec<_>>();
+    let atlas = pack_sheets(&sheet_frames)?;
+
+    let char_animation_entries = char_animation_entries
+        .into_iter()
+        .map(|(anim_key, (sheet_key, mut entry))| {
+            let packed = &atlas.sheets[&sheet_key];
+            entry.page = packed.page;
+            entry.frames = packed.frames.iter().copied().map(FrameRect::from).collect();
+            entry.crop_offsets = sheets[&sheet_key].crop_offsets.clone();
+            (anim_key, entry)
+        })
+        .collect();
+
+    let pages = atlas
+        .pages
+        .iter()
+        .enumerate()
+        .map(|(page, image)| {
+            encode_png(image).map_err(|source| CharAnimationError::Compress { page, source })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
 
     let char_animation = CharAnimationFile {
+        pages,
         anim: char_animation_entries,
     };
-    let _ = char_animation.save(&mut char_animation_file);
+    write_atomically(output_path, |char_animation_file| {
+        char_animation.save(char_animation_file)
+    })
+    .map_err(CharAnimationError::Write)
+}
+
+/// The textures the `AnimData.xml` of the sprite folder refers to, copies use the textures of
+/// the animation they copy
+pub fn required_textures(source_directory: &Path) -> Result<Vec<PathBuf>, CharAnimationError> {
+    let anim_data_path = source_directory.join("AnimData.xml");
+    let anim_data_content =
+        fs::read(&anim_data_path).map_err(|source| CharAnimationError::Read {
+            path: anim_data_path,
+            source,
+        })?;
+    let anim_data = AnimData::parse_from_xml(&anim_data_content)?;
+
+    let mut textures = anim_data
+        .anims
+        .anim
+        .keys()
+        .flat_map(|anim_key| {
+            let anim_key_str: &'static str = anim_data.get(anim_key).value().name.into();
+            ["Anim", "Offsets", "Shadow"]
+                .map(|kind| source_directory.join(format!("{anim_key_str}-{kind}.png")))
+        })
+        .collect::<Vec<_>>();
+    textures.sort();
+    textures.dedup();
+
+    Ok(textures)
+}
+
+/// An animation sheet with its frames cropped to their opaque pixels
+struct TrimmedSheet {
+    texture: RgbaImage,
+    frames: Vec<URect>,
+    /// Position of each cropped frame in the full frame
+    crop_offsets: Vec<UVec2>,
+}
+
+impl TrimmedSheet {
+    fn new(texture: RgbaImage, full_frames: &[URect]) -> Self {
+        let frames = full_frames
+            .iter()
+            .map(|frame| opaque_bounds(&texture, *frame))
+            .collect::<Vec<_>>();
+        let crop_offsets = frames
+            .iter()
+            .zip(full_frames)
+            .map(|(frame, full_frame)| frame.min - full_frame.min)
+            .collect();
+
+        Self {
+            texture,
+            frames,
+            crop_offsets,
+        }
+    }
+}
+
+/// The smallest rect holding every visible pixel of the frame, a single pixel for empty frames
+fn opaque_bounds(texture: &RgbaImage, frame: URect) -> URect {
+    let mut bounds: Option<URect> = None;
+    for y in frame.min.y..frame.max.y.min(texture.height()) {
+        for x in frame.min.x..frame.max.x.min(texture.width()) {
+            if texture.get_pixel(x, y).0[3] == 0 {
+                continue;
+            }
+            let pixel = URect::new(x, y, x + 1, y + 1);
+            bounds = Some(bounds.map_or(pixel, |bounds| bounds.union(pixel)));
+        }
+    }
+
+    bounds.unwrap_or(URect::from_corners(frame.min, frame.min + UVec2::ONE))
+}
+
+/// The frames of the animation sheet, row by row with one row per orientation
+fn sheet_frames(anim_info: &AnimInfo) -> Vec<URect> {
+    let tile_size = anim_info.tile_size();
+    let rows = anim_info.orientations().count() as u32;
+    let columns = anim_info.columns() as u32;
+
+    (0..rows)
+        .flat_map(|row| {
+            (0..columns).map(move |column| {
+                let min = UVec2::new(column, row) * tile_size;
+                URect::from_corners(min, min + tile_size)
+            })
+        })
+        .collect()
+}
+
+fn open_texture(path: &Path) -> Result<RgbaImage, CharAnimationError> {
+    image::open(path)
+        .map(|texture| texture.to_rgba8())
+        .map_err(|source| CharAnimationError::Texture {
+            path: path.to_path_buf(),
+            source,
+        })
+}
+
+/// Open a texture of the animation, it must hold every frame of the `AnimData.xml` grid
+fn open_sheet(path: &Path, sheet_size: UVec2) -> Result<RgbaImage, CharAnimationError> {
+    let texture = open_texture(path)?;
+    let found = UVec2::new(texture.width(), texture.height());
+    if found.cmplt(sheet_size).any() {
+        return Err(CharAnimationError::TextureSize {
+            path: path.to_path_buf(),
+            expected: sheet_size,
+            found,
+        });
+    }
+    Ok(texture)
 }
 
 fn extract_shadow_offset(
     anim_info: &AnimInfo,
     atlas_image: &ImageBuffer<Rgba<u8>, Vec<u8>>,
     texture: URect,
-) -> Vec2 {
+) -> Option<Vec2> {
     let tile_size = anim_info.tile_size();
 
     for y in (texture.min.y)..texture.max.y {
@@ -131,19 +347,19 @@ fn extract_shadow_offset(
             let real_y: i32 = (y - texture.min.y).try_into().unwrap();
 
             if *pixel == Rgba([255, 255, 255, 255]) {
-                return calculate_offset(real_x, real_y, tile_size);
+                return Some(calculate_offset(real_x, real_y, tile_size));
             }
         }
     }
 
-    panic!("Unable to find the shadow offsets for {:?}", anim_info);
+    None
 }
 
 fn extract_offsets(
     anim_info: &AnimInfo,
     atlas_image: &ImageBuffer<Rgba<u8>, Vec<u8>>,
     texture: URect,
-) -> CharAnimationOffsets {
+) -> Result<CharAnimationOffsets, usize> {
     let tile_size = anim_info.tile_size();
 
     let mut offsets = CharAnimationOffsets::default();
@@ -173,14 +389,12 @@ fn extract_offsets(
         }
     }
 
+    // The number of parts found otherwise
     if part_counter != 4 {
-        panic!(
-            "Could'nt find all the offsets part {part_counter} for {:?}",
-            anim_info
-        );
+        return Err(part_counter);
     }
 
-    offsets
+    Ok(offsets)
 }
 
 fn calculate_offset(real_x: i32, real_y: i32, tile_size: UVec2) -> Vec2 {
@@ -189,3 +403,51 @@ fn calculate_offset(real_x: i32, real_y: i32, tile_size: UVec2) -> Vec2 {
 
     (coordinates - half_tile_size).as_vec2()
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use bevy_math::{URect, UVec2};
+    use image::{Rgba, RgbaImage};
+
+    use super::{opaque_bounds, open_sheet, CharAnimationError};
+
+    #[test]
+    fn rejects_a_texture_smaller_than_the_sheet() {
+        let path = std::env::temp_dir().join(format!("small_sheet_{}.png", std::process::id()));
+        RgbaImage::new(8, 4).save(&path).unwrap();
+
+        let result = open_sheet(&path, UVec2::new(8, 8));
+        let fitting = open_sheet(&path, UVec2::new(8, 4));
+        fs::remove_file(&path).unwrap();
+
+        assert!(
+            matches!(
+                result,
+                Err(CharAnimationError::TextureSize { expected, found, .. })
+                    if expected == UVec2::new(8, 8) && found == UVec2::new(8, 4)
+            ),
+            "{result:?}"
+        );
+        assert!(fitting.is_ok());
+    }
+
+    #[test]
+    fn crops_the_frame_to_its_visible_pixels() {
+        let mut texture = RgbaImage::new(16, 8);
+        texture.put_pixel(10, 2, Rgba([255, 0, 0, 255]));
+        texture.put_pixel(13, 5, Rgba([0, 0, 255, 128]));
+
+        let bounds = opaque_bounds(&texture, URect::new(8, 0, 16, 8));
+        assert_eq!(bounds, URect::new(10, 2, 14, 6));
+    }
+
+    #[test]
+    fn empty_frame_falls_back_to_a_single_pixel() {
+        let texture = RgbaImage::new(16, 8);
+
+        let bounds = opaque_bounds(&texture, URect::new(8, 0, 16, 8));
+        assert_eq!(bounds, URect::new(8, 0, 9, 1));
+    }
+}
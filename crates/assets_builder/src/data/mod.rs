@@ -1,3 +1,10 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use thiserror::Error;
+
 pub mod common_data;
 pub mod pokemon_data;
 pub mod pokemons;
@@ -5,3 +12,30 @@ pub mod spell_data;
 pub mod spells;
 
 pub use pokemons::*;
+
+#[derive(Debug, Error)]
+pub enum DataError {
+    #[error("failed to read {path}: {source}")]
+    Read { path: PathBuf, source: io::Error },
+    #[error("failed to parse the JSON data: {0}")]
+    Parse(#[from] serde_json::Error),
+    #[error("failed to write the data: {0}")]
+    Write(#[source] io::Error),
+}
+
+/// Read a raw JSON data file, without its UTF-8 BOM if any
+pub(crate) fn read_json(path: &Path) -> Result<Vec<u8>, DataError> {
+    // Read the file to a Vec<u8>
+    let mut data = fs::read(path).map_err(|source| DataError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    // UTF-8 BOM is three bytes: EF BB BF
+    if data.starts_with(&[0xEF, 0xBB, 0xBF]) {
+        // Remove the first three bytes (the BOM)
+        data = data[3..].to_vec();
+    }
+
+    Ok(data)
+}
@@ -2,6 +2,9 @@ use std::collections::HashMap;
 
 use common::element::Element;
 use serde::{Deserialize, Serialize};
+use spell_data::{
+    SpellCategory, SpellData, SpellHitbox, SpellRays, SpellTargets, SpellTerrain, SpellTileEffect,
+};
 
 use super::common_data::RawTextData;
 
@@ -17,6 +20,59 @@ impl RawSpellData {
         let font_data: RawSpellData = serde_json::from_reader(spell_data)?;
         Ok(font_data)
     }
+
+    /// The move as played by the game, `None` when its hitbox is not supported yet
+    pub fn to_data(&self) -> Option<SpellData> {
+        let object = &self.object;
+        let hitbox = object.hitbox_action.to_data()?;
+        let explosion = &object.explosion;
+        let hit_tiles = object.hitbox_action.hit_tiles() || explosion.hit_tiles;
+
+        Some(SpellData {
+            name: object.name.default_text.to_owned(),
+            category: match object.data.category {
+                1 => SpellCategory::Physical,
+                2 => SpellCategory::Special,
+                _ => SpellCategory::Status,
+            },
+            power: object
+                .data
+                .skill_states
+                .iter()
+                .find_map(|state| state.power)
+                .unwrap_or(0),
+            strikes: object.strikes.max(1) as u32,
+            hitbox,
+            cast_visual_effect: object
+                .data
+                .intro_fx
+                .iter()
+                .find_map(|fx| fx.emitter.anim_index())
+                .or_else(|| object.hitbox_action.action_fx().emitter.anim_index())
+                .map(str::to_owned),
+            hit_visual_effect: object.data.hit_fx.emitter.anim_index().map(str::to_owned),
+            explosion: (explosion.range > 0).then(|| spell_data::SpellExplosion {
+                range: explosion.range,
+                targets: target_alignments(explosion.target_alignments as u32),
+                visual_effect: explosion
+                    .explode_fx
+                    .emitter
+                    .anim_index()
+                    .or_else(|| explosion.emitter.anim_index())
+                    .or_else(|| explosion.tile_emitter.anim_index())
+                    .map(str::to_owned),
+            }),
+            tile_effects: match hit_tiles {
+                true => object
+                    .data
+                    .on_hit_tiles
+                    .iter()
+                    .filter_map(|event| event.value.to_tile_effect())
+                    .collect(),
+                false => vec![],
+            },
+        })
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -89,6 +145,21 @@ pub struct Anim {
     pub anim_flip: i32,
 }
 
+impl Anim {
+    fn index(&self) -> Option<&str> {
+        (!self.anim_index.is_empty()).then_some(self.anim_index.as_str())
+    }
+}
+
+/// The `TargetAlignments` bits: 1 for the caster, 2 for its allies and 4 for its foes
+fn target_alignments(alignments: u32) -> SpellTargets {
+    SpellTargets {
+        caster: alignments & 1 != 0,
+        allies: alignments & 2 != 0,
+        foes: alignments & 4 != 0,
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct ScreenMovement {
@@ -568,6 +639,44 @@ pub enum BaseEvent {
     SwapAbility,
 }
 
+impl BaseEvent {
+    /// The tile effect of an `OnHitTiles` event, `None` for the events the game can't play
+    fn to_tile_effect(&self) -> Option<SpellTileEffect> {
+        match self {
+            BaseEvent::RemoveTerrainState { states, .. } => {
+                let terrains = states
+                    .iter()
+                    .filter_map(EventState::to_terrain)
+                    .collect::<Vec<_>>();
+                (!terrains.is_empty()).then_some(SpellTileEffect::ClearTerrain(terrains))
+            }
+            BaseEvent::RemoveTerrain { tile_types, .. }
+            | BaseEvent::ShatterTerrain { tile_types } => {
+                let terrains = tile_types
+                    .iter()
+                    .filter_map(|tile_type| tile_terrain(tile_type))
+                    .collect::<Vec<_>>();
+                (!terrains.is_empty()).then_some(SpellTileEffect::ClearTerrain(terrains))
+            }
+            BaseEvent::SetTrap { trap_id } => Some(SpellTileEffect::SetTrap(trap_id.to_owned())),
+            BaseEvent::RemoveTrap => Some(SpellTileEffect::RemoveTrap),
+            _ => None,
+        }
+    }
+}
+
+/// The terrain of a raw tile ID
+fn tile_terrain(tile_type: &str) -> Option<SpellTerrain> {
+    match tile_type {
+        "wall" => Some(SpellTerrain::Wall),
+        "water" => Some(SpellTerrain::Water),
+        "lava" => Some(SpellTerrain::Lava),
+        "pit" => Some(SpellTerrain::Abyss),
+        "grass" => Some(SpellTerrain::Foliage),
+        _ => None,
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Hash, Clone, Copy)]
 pub enum TerrainType {
     #[serde(rename = "electric_terrain")]
@@ -635,6 +744,19 @@ pub enum EventState {
     MajorStatus,
 }
 
+impl EventState {
+    fn to_terrain(&self) -> Option<SpellTerrain> {
+        match self {
+            EventState::WallTerrain => Some(SpellTerrain::Wall),
+            EventState::WaterTerrain => Some(SpellTerrain::Water),
+            EventState::LavaTerrain => Some(SpellTerrain::Lava),
+            EventState::AbyssTerrain => Some(SpellTerrain::Abyss),
+            EventState::FoliageTerrain => Some(SpellTerrain::Foliage),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct SkillState {
@@ -812,6 +934,105 @@ pub enum HitboxAction {
     },
 }
 
+impl HitboxAction {
+    /// Area, throw and offset hitboxes can't be played yet
+    fn to_data(&self) -> Option<SpellHitbox> {
+        match self {
+            HitboxAction::Attack { .. } => Some(SpellHitbox::Melee),
+            HitboxAction::Projectile {
+                anim,
+                emitter,
+                stream_emitter,
+                rays,
+                boomerang,
+                range,
+                stop_at_hit,
+                stop_at_wall,
+                ..
+            } => Some(SpellHitbox::Projectile {
+                range: *range,
+                rays: match rays {
+                    1 => SpellRays::Three,
+                    2 => SpellRays::Five,
+                    3 => SpellRays::Four,
+                    4 => SpellRays::Eight,
+                    _ => SpellRays::One,
+                },
+                stop_at_wall: *stop_at_wall,
+                stop_at_hit: *stop_at_hit,
+                boomerang: *boomerang,
+                visual_effect: anim
+                    .index()
+                    .or_else(|| stream_emitter.anim_index())
+                    .or_else(|| emitter.anim_index())
+                    .map(str::to_owned),
+            }),
+            HitboxAction::Dash {
+                anim,
+                emitter,
+                range,
+                stop_at_hit,
+                stop_at_wall,
+                ..
+            } => Some(SpellHitbox::Projectile {
+                range: *range,
+                rays: SpellRays::One,
+                stop_at_wall: *stop_at_wall,
+                stop_at_hit: *stop_at_hit,
+                boomerang: false,
+                visual_effect: anim
+                    .index()
+                    .or_else(|| emitter.anim_index())
+                    .map(str::to_owned),
+            }),
+            HitboxAction::WaveMotion {
+                anim,
+                range,
+                stop_at_hit,
+                stop_at_wall,
+                ..
+            } => Some(SpellHitbox::Projectile {
+                range: *range,
+                rays: SpellRays::One,
+                stop_at_wall: *stop_at_wall,
+                stop_at_hit: *stop_at_hit,
+                boomerang: false,
+                visual_effect: anim.index().map(str::to_owned),
+            }),
+            HitboxAction::SelfAction { .. } => Some(SpellHitbox::Caster),
+            HitboxAction::Area { .. }
+            | HitboxAction::Throw { .. }
+            | HitboxAction::Offset { .. } => None,
+        }
+    }
+
+    /// The tile effects only apply when the hitbox or the explosion hits the tiles
+    fn hit_tiles(&self) -> bool {
+        match self {
+            HitboxAction::Projectile { hit_tiles, .. }
+            | HitboxAction::Dash { hit_tiles, .. }
+            | HitboxAction::Attack { hit_tiles, .. }
+            | HitboxAction::Area { hit_tiles, .. }
+            | HitboxAction::WaveMotion { hit_tiles, .. }
+            | HitboxAction::Offset { hit_tiles, .. } => *hit_tiles,
+            HitboxAction::SelfAction { .. } | HitboxAction::Throw { .. } => false,
+        }
+    }
+
+    fn action_fx(&self) -> &Fx {
+        match self {
+            HitboxAction::Projectile { action_fx, .. }
+            | HitboxAction::Dash { action_fx, .. }
+            | HitboxAction::Attack { action_fx, .. }
+            | HitboxAction::SelfAction { action_fx, .. }
+            | HitboxAction::Area { action_fx, .. }
+            | HitboxAction::WaveMotion { action_fx, .. }
+            | HitboxAction::Throw { action_fx, .. }
+            | HitboxAction::Offset { action_fx, .. } => action_fx,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(tag = "$type")]
 pub enum Emitter {
@@ -1162,3 +1383,50 @@ pub enum Emitter {
         layer: i64,
     },
 }
+
+impl Emitter {
+    /// The first visual effect played by the emitter, by its `AnimIndex`
+    pub fn anim_index(&self) -> Option<&str> {
+        match self {
+            Emitter::Single { anim, .. } | Emitter::Repeat { anim, .. } => anim.anim_index(),
+            Emitter::Between {
+                anim_back,
+                anim_front,
+                ..
+            } => anim_front.anim_index().or_else(|| anim_back.anim_index()),
+            Emitter::Stream { anims, .. }
+            | Emitter::AttachRelease { anims, .. }
+            | Emitter::CircleSquareSprinkle { anims, .. }
+            | Emitter::FiniteRelease { anims, .. }
+            | Emitter::CircleSquareRelease { anims, .. }
+            | Emitter::CircleArea { anims, .. }
+            | Emitter::CircleSquareFountain { anims, .. }
+            | Emitter::FiniteArea { anims, .. }
+            | Emitter::StaticArea { anims, .. }
+            | Emitter::FiniteReleaseRange { anims, .. }
+            | Emitter::AttachArea { anims, .. }
+            | Emitter::SqueezedArea { anims, .. }
+            | Emitter::FiniteSprinkle { anims, .. }
+            | Emitter::AttachReleaseRange { anims, .. }
+            | Emitter::MultiCircleSquare {
+                emitters: anims, ..
+            } => anims.iter().find_map(Emitter::anim_index),
+            Emitter::MoveTo { anim, .. }
+            | Emitter::FiniteOverlay { anim, .. }
+            | Emitter::Static { anim, .. }
+            | Emitter::Emitting { anim, .. }
+            | Emitter::Particle { anim, .. }
+            | Emitter::SwingSwitch { anim, .. }
+            | Emitter::Column { anim, .. } => anim.index(),
+            Emitter::Clamp { anim1, anim2, .. } => anim1.index().or_else(|| anim2.index()),
+            Emitter::FiniteGather { anims, .. } | Emitter::Vortex { anims, .. } => {
+                anims.iter().find_map(Anim::index)
+            }
+            Emitter::EmptyAttach
+            | Emitter::EmptyFinite { .. }
+            | Emitter::EmptyCircleSquare { .. }
+            | Emitter::EmptyShooting { .. }
+            | Emitter::AfterImage { .. } => None,
+        }
+    }
+}
@@ -1,46 +1,20 @@
-use crate::utils::list_files_in_folder;
-use std::collections::HashMap;
-use std::fs::{self, File};
+use std::fs;
 use std::path::Path;
 
-use super::spell_data::RawSpellData;
+use crate::utils::write_atomically;
 
-pub fn create_spell_data(source_directory: &Path, output_directory: &str) {
-    let output_directory = Path::new(output_directory);
-    fs::create_dir_all(output_directory).unwrap();
+use super::{read_json, spell_data::RawSpellData, DataError};
 
-    let spell_data_files = list_files_in_folder(source_directory, Some("json"))
-        .expect("Failed to load pokemon data folder");
+pub fn create_spell_data(source_file: &Path, output_filename: &str) -> Result<(), DataError> {
+    let content = read_json(source_file)?;
+    let Some(data) = RawSpellData::parse_from_json(&content)?.to_data() else {
+        println!("Skipping {output_filename}, its hitbox is not supported yet");
+        return Ok(());
+    };
 
-    let spell_data = spell_data_files
-        .iter()
-        .map(|file| {
-            let path = Path::new(file);
-            println!("{file}");
+    println!("Creating {output_filename}");
+    let output_path = Path::new(output_filename);
+    fs::create_dir_all(output_path.parent().unwrap()).map_err(DataError::Write)?;
 
-            // Read the file to a Vec<u8>
-            let mut data = fs::read(path).unwrap();
-
-            // UTF-8 BOM is three bytes: EF BB BF
-            if data.starts_with(&[0xEF, 0xBB, 0xBF]) {
-                // Remove the first three bytes (the BOM)
-                data = data[3..].to_vec();
-            }
-
-            (path, data)
-        })
-        .map(|(path, content)| (path, RawSpellData::parse_from_json(&content).unwrap()))
-        // .map(|(path, data)| (path, data.to_data()))
-        .collect::<HashMap<_, _>>();
-
-    println!("{:?}", spell_data);
-
-    // for (path, data) in spell_data {
-    //     let file_stem = path.file_stem().unwrap().to_str().unwrap();
-    //     let file_name = format!("{file_stem}.sd.ron");
-
-    //     let output_file = output_directory.join(file_name);
-    //     let mut output_file = File::create(output_file).unwrap();
-    //     let _ = data.save(&mut output_file);
-    // }
+    write_atomically(output_path, |output_file| data.save(output_file)).map_err(DataError::Write)
 }
@@ -0,0 +1,94 @@
+use std::{collections::HashMap, fs};
+
+use glob::{Pattern, PatternError};
+
+use crate::{utils::parse_pokemon_conversions, POKEMON_CONVERSION_PATH};
+
+/// Parse a CLI glob pattern, the numeric ones are padded like the pokemon IDs so that `25`
+/// selects `0025`
+pub fn parse_pattern(value: &str) -> Result<Pattern, PatternError> {
+    match value.parse::<u32>() {
+        Ok(id) => Pattern::new(&format!("{id:04}")),
+        Err(_) => Pattern::new(value),
+    }
+}
+
+/// Glob patterns selecting the assets to build, everything is selected without any pattern
+#[derive(Debug, Clone, Default)]
+pub struct AssetFilter {
+    patterns: Vec<Pattern>,
+}
+
+impl AssetFilter {
+    pub fn new(patterns: Vec<Pattern>) -> Self {
+        Self { patterns }
+    }
+
+    pub fn is_active(&self) -> bool {
+        !self.patterns.is_empty()
+    }
+
+    /// Whether any of the names of the asset is selected
+    pub fn matches<'a>(&self, names: impl IntoIterator<Item = &'a str> + Clone) -> bool {
+        !self.is_active()
+            || self
+                .patterns
+                .iter()
+                .any(|pattern| names.clone().into_iter().any(|name| pattern.matches(name)))
+    }
+}
+
+/// The assets selected on the command line, pokemons are selected by ID or by name
+#[derive(Debug, Default)]
+pub struct Selection {
+    pub pokemons: AssetFilter,
+    pub fonts: AssetFilter,
+    pub spells: AssetFilter,
+    pokemon_names: HashMap<u32, String>,
+}
+
+impl Selection {
+    pub fn new(pokemons: AssetFilter, fonts: AssetFilter, spells: AssetFilter) -> Self {
+        // Without the conversion table the pokemons can only be selected by the name of their
+        // files
+        let pokemon_names = match pokemons.is_active() {
+            true => fs::read_to_string(POKEMON_CONVERSION_PATH)
+                .map(|conversions| {
+                    parse_pokemon_conversions(&conversions)
+                        .filter_map(Result::ok)
+                        .map(|(id, name)| (id, name.to_string()))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            false => HashMap::new(),
+        };
+
+        Self {
+            pokemons,
+            fonts,
+            spells,
+            pokemon_names,
+        }
+    }
+
+    /// The pokemon with this `0004` like ID
+    pub fn pokemon_id(&self, id: &str) -> bool {
+        let name = id
+            .parse::<u32>()
+            .ok()
+            .and_then(|id| self.pokemon_names.get(&id));
+        self.pokemons
+            .matches([id].into_iter().chain(name.map(String::as_str)))
+    }
+
+    /// The pokemon with this name
+    pub fn pokemon_name(&self, name: &str) -> bool {
+        let id = self
+            .pokemon_names
+            .iter()
+            .find(|(_, pokemon_name)| *pokemon_name == name)
+            .map(|(id, _)| format!("{id:04}"));
+        self.pokemons
+            .matches([name].into_iter().chain(id.as_deref()))
+    }
+}
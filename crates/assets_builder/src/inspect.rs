@@ -0,0 +1,388 @@
+use std::{
+    fs,
+    io::{self, Cursor},
+    path::{Path, PathBuf},
+};
+
+use bevy_math::{UVec2, Vec2};
+use bitmap_font::bfn::{Font, FontFileError};
+use char_animation::{
+    anim_key::AnimKey,
+    file::{CharAnimationFile, CharAnimationFileEntry, CharAnimationFileError},
+    orientation::Orientation,
+};
+use image::{GenericImage, GenericImageView, ImageError, ImageReader, Rgba, RgbaImage};
+use strum::IntoEnumIterator;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum InspectError {
+    #[error("failed to read {path}: {source}")]
+    Read { path: PathBuf, source: io::Error },
+    #[error("{0}")]
+    CharAnimation(#[from] CharAnimationFileError),
+    #[error("{0}")]
+    Font(#[from] FontFileError),
+    #[error("failed to decode atlas page {page}: {source}")]
+    Texture { page: usize, source: ImageError },
+    #[error("{anim} is on the atlas page {page}, missing from the file")]
+    MissingPage { anim: AnimKey, page: usize },
+    #[error("frame {frame} of {anim} lies outside its atlas page {page}")]
+    FrameOutOfPage {
+        anim: AnimKey,
+        frame: usize,
+        page: usize,
+    },
+    #[error("failed to write {path}: {source}")]
+    Write { path: PathBuf, source: ImageError },
+    #[error("{0} is neither a .chara nor a .bfn file")]
+    UnknownFile(PathBuf),
+}
+
+/// The built files the tools can read
+enum BuiltFile {
+    CharAnimation(CharAnimationFile),
+    Font(Font),
+}
+
+impl BuiltFile {
+    fn load(path: &Path) -> Result<Self, InspectError> {
+        let extension = path.extension().and_then(|extension| extension.to_str());
+        if !matches!(extension, Some("chara" | "bfn")) {
+            return Err(InspectError::UnknownFile(path.to_path_buf()));
+        }
+
+        let buffer = fs::read(path).map_err(|source| InspectError::Read {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        match extension {
+            Some("chara") => Ok(Self::CharAnimation(CharAnimationFile::load(&buffer)?)),
+            _ => Ok(Self::Font(Font::load(&buffer)?)),
+        }
+    }
+}
+
+/// Print the content of a `.chara` or `.bfn` file
+pub fn inspect(path: &Path) -> Result<(), InspectError> {
+    match BuiltFile::load(path)? {
+        BuiltFile::CharAnimation(char_animation) => inspect_char_animation(path, &char_animation),
+        BuiltFile::Font(font) => {
+            inspect_font(path, &font);
+            Ok(())
+        }
+    }
+}
+
+/// Export the animations of a `.chara` as PNG sheets with their offsets marked, or the atlas of
+/// a `.bfn`
+pub fn export(path: &Path, output_directory: &Path) -> Result<(), InspectError> {
+    fs::create_dir_all(output_directory).map_err(|source| InspectError::Write {
+        path: output_directory.to_path_buf(),
+        source: ImageError::IoError(source),
+    })?;
+
+    match BuiltFile::load(path)? {
+        BuiltFile::CharAnimation(char_animation) => {
+            let pages = decode_pages(&char_animation)?;
+            for (anim_key, entry) in sorted_entries(&char_animation) {
+                let page = pages.get(entry.page).ok_or(InspectError::MissingPage {
+                    anim: *anim_key,
+                    page: entry.page,
+                })?;
+                let output = output_directory.join(format!("{anim_key}.png"));
+                save(&output, &export_sheet(*anim_key, entry, page)?)?;
+            }
+        }
+        BuiltFile::Font(font) => {
+            let atlas = image::load_from_memory(&font.texture)
+                .map_err(|source| InspectError::Texture { page: 0, source })?;
+            save(
+                &output_directory.join(format!("{}.png", font.name)),
+                &atlas.to_rgba8(),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn inspect_char_animation(
+    path: &Path,
+    char_animation: &CharAnimationFile,
+) -> Result<(), InspectError> {
+    let page_sizes = char_animation
+        .pages
+        .iter()
+        .enumerate()
+        .map(|(page, bytes)| {
+            ImageReader::new(Cursor::new(bytes))
+                .with_guessed_format()
+                .map_err(ImageError::IoError)
+                .and_then(|reader| reader.into_dimensions())
+                .map_err(|source| InspectError::Texture { page, source })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    println!(
+        "{}: {} animations on {} atlas pages",
+        path.display(),
+        char_animation.anim.len(),
+        page_sizes.len()
+    );
+    for (page, (width, height)) in page_sizes.iter().enumerate() {
+        println!("  page {page}: {width}x{height}");
+    }
+
+    for (anim_key, entry) in sorted_entries(char_animation) {
+        let columns = entry.durations.len();
+        println!(
+            "{anim_key} (index {}): {} orientations of {columns} frames, {}x{} on page {}",
+            entry.index,
+            entry.frames.len() / columns.max(1),
+            entry.frame_width,
+            entry.frame_height,
+            entry.page
+        );
+        println!("  durations: {:?}", entry.durations);
+        println!(
+            "  rush frame: {}, hit frame: {}, return frame: {}",
+            frame_label(entry.rush_frame),
+            frame_label(entry.hit_frame),
+            frame_label(entry.return_frame)
+        );
+
+        for orientation in Orientation::iter() {
+            let (Some(offsets), Some(shadow_offsets)) = (
+                entry.offsets.get(&orientation),
+                entry.shadow_offsets.get(&orientation),
+            ) else {
+                continue;
+            };
+            for (frame, (offsets, shadow)) in offsets.iter().zip(shadow_offsets).enumerate() {
+                println!(
+                    "  {orientation} {frame}: body {} head {} left {} right {} shadow {}",
+                    offsets.body, offsets.head, offsets.left, offsets.right, shadow
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+fn inspect_font(path: &Path, font: &Font) {
+    println!(
+        "{}: font `{}` with {} glyphs on a {}x{} atlas",
+        path.display(),
+        font.name,
+        font.glyph_count,
+        font.size.0,
+        font.size.1
+    );
+    println!(
+        "  char space: {}, space width: {}, char height: {}, line space: {}",
+        font.char_space, font.space_width, font.char_height, font.line_space
+    );
+
+    let mut glyphs = font.glyphs.values().collect::<Vec<_>>();
+    glyphs.sort_by_key(|glyph| glyph.code_point);
+    for glyph in glyphs {
+        let character = char::from_u32(glyph.code_point)
+            .filter(|character| !character.is_control())
+            .unwrap_or(char::REPLACEMENT_CHARACTER);
+        println!(
+            "  U+{:04X} {character:?}: {}x{} at ({}, {}){}",
+            glyph.code_point,
+            glyph.bounds.width,
+            glyph.bounds.height,
+            glyph.bounds.x,
+            glyph.bounds.y,
+            if glyph.colorless { ", colorless" } else { "" }
+        );
+    }
+}
+
+fn frame_label(frame: Option<usize>) -> String {
+    frame.map_or_else(|| "-".to_string(), |frame| frame.to_string())
+}
+
+/// The animations in the order of their index
+fn sorted_entries(char_animation: &CharAnimationFile) -> Vec<(&AnimKey, &CharAnimationFileEntry)> {
+    let mut entries = char_animation.anim.iter().collect::<Vec<_>>();
+    entries.sort_by_key(|(anim_key, entry)| (entry.index, anim_key.to_string()));
+    entries
+}
+
+fn decode_pages(char_animation: &CharAnimationFile) -> Result<Vec<RgbaImage>, InspectError> {
+    char_animation
+        .pages
+        .iter()
+        .enumerate()
+        .map(|(page, bytes)| {
+            image::load_from_memory(bytes)
+                .map(|image| image.to_rgba8())
+                .map_err(|source| InspectError::Texture { page, source })
+        })
+        .collect()
+}
+
+/// The uncropped frames of the animation on a grid, one row per orientation, with the offsets
+/// drawn in the colors of the raw `-Offsets` and `-Shadow` sheets
+fn export_sheet(
+    anim: AnimKey,
+    entry: &CharAnimationFileEntry,
+    page: &RgbaImage,
+) -> Result<RgbaImage, InspectError> {
+    let frame_size = UVec2::new(entry.frame_width, entry.frame_height);
+    let columns = entry.durations.len().max(1);
+    let rows = entry.frames.len().div_ceil(columns);
+    let mut sheet = RgbaImage::new(frame_size.x * columns as u32, frame_size.y * rows as u32);
+
+    for (index, (frame, crop_offset)) in entry.frames.iter().zip(&entry.crop_offsets).enumerate() {
+        let (column, row) = (index % columns, index / columns);
+        let origin = UVec2::new(column as u32, row as u32) * frame_size;
+        let is_in_page = frame
+            .x
+            .checked_add(frame.width)
+            .is_some_and(|right| right <= page.width())
+            && frame
+                .y
+                .checked_add(frame.height)
+                .is_some_and(|bottom| bottom <= page.height());
+        if !is_in_page {
+            return Err(InspectError::FrameOutOfPage {
+                anim,
+                frame: index,
+                page: entry.page,
+            });
+        }
+        let frame_image = page
+            .view(frame.x, frame.y, frame.width, frame.height)
+            .to_image();
+        let target = origin + *crop_offset;
+        let _ = sheet.copy_from(&frame_image, target.x, target.y);
+
+        let Some(orientation) = Orientation::iter().nth(row) else {
+            continue;
+        };
+        if let Some(offsets) = entry
+            .offsets
+            .get(&orientation)
+            .and_then(|offsets| offsets.get(column))
+        {
+            for (offset, color) in [
+                (offsets.head, [0, 0, 0, 255]),
+                (offsets.left, [255, 0, 0, 255]),
+                (offsets.body, [0, 255, 0, 255]),
+                (offsets.right, [0, 0, 255, 255]),
+            ] {
+                mark_offset(&mut sheet, origin, frame_size, offset, color);
+            }
+        }
+        if let Some(shadow) = entry
+            .shadow_offsets
+            .get(&orientation)
+            .and_then(|shadow_offsets| shadow_offsets.get(column))
+        {
+            mark_offset(
+                &mut sheet,
+                origin,
+                frame_size,
+                *shadow,
+                [255, 255, 255, 255],
+            );
+        }
+    }
+    Ok(sheet)
+}
+
+/// Offsets are relative to the center of the frame with the y axis going up
+fn mark_offset(
+    sheet: &mut RgbaImage,
+    origin: UVec2,
+    frame_size: UVec2,
+    offset: Vec2,
+    color: [u8; 4],
+) {
+    let half_frame_size = (frame_size / 2).as_ivec2();
+    let x = offset.x as i32 + half_frame_size.x;
+    let y = frame_size.y as i32 - (offset.y as i32 + half_frame_size.y);
+    if (0..frame_size.x as i32).contains(&x) && (0..frame_size.y as i32).contains(&y) {
+        sheet.put_pixel(origin.x + x as u32, origin.y + y as u32, Rgba(color));
+    }
+}
+
+fn save(path: &Path, image: &RgbaImage) -> Result<(), InspectError> {
+    println!("Exporting {}", path.display());
+    image.save(path).map_err(|source| InspectError::Write {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use bevy_math::UVec2;
+    use char_animation::{
+        anim_key::AnimKey,
+        file::{CharAnimationFileEntry, FrameRect},
+    };
+    use image::RgbaImage;
+
+    use super::{export_sheet, InspectError};
+
+    fn entry(frame: FrameRect) -> CharAnimationFileEntry {
+        CharAnimationFileEntry {
+            page: 0,
+            frames: vec![frame],
+            crop_offsets: vec![UVec2::ZERO],
+            index: 0,
+            frame_width: 8,
+            frame_height: 8,
+            durations: vec![1],
+            is_single_orientation: true,
+            rush_frame: None,
+            hit_frame: None,
+            return_frame: None,
+            shadow_offsets: HashMap::new(),
+            offsets: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn exports_the_frames_inside_their_page() {
+        let frame = FrameRect {
+            x: 8,
+            y: 8,
+            width: 8,
+            height: 8,
+        };
+
+        let sheet = export_sheet(AnimKey::Walk, &entry(frame), &RgbaImage::new(16, 16)).unwrap();
+        assert_eq!(sheet.dimensions(), (8, 8));
+    }
+
+    #[test]
+    fn rejects_a_frame_outside_its_page() {
+        let frame = FrameRect {
+            x: 12,
+            y: 0,
+            width: 8,
+            height: 8,
+        };
+
+        let result = export_sheet(AnimKey::Walk, &entry(frame), &RgbaImage::new(16, 16));
+        assert!(
+            matches!(
+                result,
+                Err(InspectError::FrameOutOfPage {
+                    anim: AnimKey::Walk,
+                    frame: 0,
+                    page: 0
+                })
+            ),
+            "{result:?}"
+        );
+    }
+}
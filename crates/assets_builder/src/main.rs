@@ -1,28 +1,58 @@
-use clap::Parser;
-use std::path::Path;
+use clap::{Parser, Subcommand};
+use std::{
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
 
 use bevy_math::UVec2;
+use glob::Pattern;
 
 mod bitmap_fonts;
 mod char_animations;
 mod data;
+mod filter;
+mod inspect;
+mod manifest;
+mod portraits;
+mod report;
 pub mod utils;
+mod validate;
+mod watch;
 
 use crate::{
     bitmap_fonts::create_bitmap_font,
-    char_animations::create_char_animation,
-    data::{create_pokemon_data, spells::create_spell_data},
-    utils::list_directories,
+    char_animations::{create_char_animation, CharAnimationError},
+    data::{create_pokemon_data, spells::create_spell_data, DataError},
+    filter::{parse_pattern, AssetFilter, Selection},
+    inspect::{export, inspect},
+    manifest::{BuildJob, BuildManifest, BuildOptions, MANIFEST_PATH},
+    portraits::{create_portrait, PortraitError},
+    report::{AssetError, BuildFailure, BuildReport},
+    utils::{list_directories, list_files_in_folder},
+    validate::validate,
+    watch::watch,
 };
 
 const FONT_RAW_FOLDER_PATH: &str = "raw_assets/fonts";
 const CHAR_ANIMATION_RAW_FOLDER_PATH: &str = "raw_assets/sprites";
 const POKEMON_DATA_RAW_FOLDER_PATH: &str = "raw_assets/data/pokemons";
 const SPELL_DATA_RAW_FOLDER_PATH: &str = "raw_assets/data/spells";
+const PORTRAIT_RAW_FOLDER_PATH: &str = "raw_assets/portraits";
+
+const FONT_FOLDER_PATH: &str = "assets/fonts";
+const CHAR_ANIMATION_FOLDER_PATH: &str = "assets/chara";
+const POKEMON_DATA_FOLDER_PATH: &str = "assets/data/pokemons";
+const SPELL_DATA_FOLDER_PATH: &str = "assets/data/spells";
+const PORTRAIT_FOLDER_PATH: &str = "assets/portraits";
+const POKEMON_CONVERSION_PATH: &str = "assets/conversions/pokemon.txt";
+const VISUAL_EFFECTS_FOLDER_PATH: &str = "assets/visual_effects";
 
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Build the char animations
     #[arg(short, long, default_value_t = false)]
     char_animation: bool,
@@ -39,62 +69,274 @@ struct Args {
     #[arg(short, long, default_value_t = false)]
     spell_data: bool,
 
+    /// Build the pokemon portraits
+    #[arg(short = 'r', long, default_value_t = false)]
+    portraits: bool,
+
     /// Build all the assets
     #[arg(short, long, default_value_t = false)]
     all: bool,
+
+    /// Rebuild the assets even if their inputs didn't change
+    #[arg(short, long, default_value_t = false)]
+    force: bool,
+
+    /// Build every asset it can instead of stopping at the first failure
+    #[arg(short, long, default_value_t = false)]
+    keep_going: bool,
+
+    /// Keep running and rebuild the selected assets when their raw files change
+    #[arg(short, long, default_value_t = false)]
+    watch: bool,
+
+    /// Only build the data, char animations and portraits of these pokemons, by ID or name, glob patterns
+    /// are allowed (e.g. `0004,0025` or `pika*`)
+    #[arg(long, value_delimiter = ',', value_parser = parse_pattern)]
+    only: Vec<Pattern>,
+
+    /// Only build these bitmap fonts, glob patterns are allowed
+    #[arg(long, value_delimiter = ',', value_parser = parse_pattern)]
+    font: Vec<Pattern>,
+
+    /// Only build the data of these spells, glob patterns are allowed
+    #[arg(long, value_delimiter = ',', value_parser = parse_pattern)]
+    spell: Vec<Pattern>,
 }
 
-fn main() {
-    // println!("hello");
-    // let char_animation_data = fs::read("assets/chara/0004.chara").unwrap();
-    // let char_animation = CharAnimationFile::load(&char_animation_data).unwrap();
-    // let anim = char_animation.anim.get(&AnimKey::Idle).unwrap();
-    // let texture_buffer = image::load_from_memory(&anim.texture)
-    //     .expect("Failed to decompress char animation texture")
-    //     .to_rgba8();
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Check the raw and built assets without writing anything
+    Validate,
+    /// Print the animations and offsets of a .chara file or the glyphs of a .bfn file
+    Inspect {
+        /// The .chara or .bfn file
+        file: PathBuf,
+    },
+    /// Export the animations of a .chara file as PNG sheets with their offsets marked, or the
+    /// atlas of a .bfn file
+    Export {
+        /// The .chara or .bfn file
+        file: PathBuf,
+        /// Folder to write the PNG files into
+        #[arg(short, long, default_value = "export")]
+        output: PathBuf,
+    },
+}
 
-    // let _ = texture_buffer.save("test.png");
+fn main() -> ExitCode {
     let args = Args::parse();
+    let tool_result = match &args.command {
+        Some(Command::Validate) => return validate(),
+        Some(Command::Inspect { file }) => Some(inspect(file)),
+        Some(Command::Export { file, output }) => Some(export(file, output)),
+        None => None,
+    };
+    if let Some(result) = tool_result {
+        return match result {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(error) => {
+                eprintln!("{error}");
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    let options = BuildOptions {
+        force: args.force,
+        keep_going: args.keep_going,
+    };
+
+    // A filter builds the assets it selects without having to enable their step
+    let selection = Selection::new(
+        AssetFilter::new(args.only),
+        AssetFilter::new(args.font),
+        AssetFilter::new(args.spell),
+    );
 
-    if args.pokemon_data || args.all {
-        build_pokemon_data();
+    let requested = [
+        args.pokemon_data,
+        args.spell_data,
+        args.char_animation,
+        args.bitmap_fonts,
+        args.portraits,
+    ];
+    let selected = selected_steps(&selection);
+    let enabled: [bool; 5] =
+        std::array::from_fn(|step| requested[step] || args.all || selected[step]);
+
+    let manifest_path = Path::new(MANIFEST_PATH);
+    let mut manifest = BuildManifest::load(manifest_path);
+    let report = run_build_steps(enabled, &mut manifest, &selection, options);
+    save_manifest(&manifest);
+    report.print();
+
+    if args.watch {
+        watch(|changes| {
+            let changed = changes.selection(&selection);
+            let changed_steps = selected_steps(&changed);
+            let steps = std::array::from_fn(|step| enabled[step] && changed_steps[step]);
+
+            let report = run_build_steps(steps, &mut manifest, &changed, options);
+            save_manifest(&manifest);
+            report.print();
+        });
     }
-    if args.spell_data || args.all {
-        build_spell_data();
+
+    if report.is_success() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
     }
-    if args.char_animation || args.all {
-        build_char_animations();
+}
+
+const BUILD_STEPS: [BuildStep; 5] = [
+    build_pokemon_data,
+    build_spell_data,
+    build_char_animations,
+    build_bitmap_fonts,
+    build_portraits,
+];
+
+/// Whether each of the [`BUILD_STEPS`] builds some explicitly selected assets
+fn selected_steps(selection: &Selection) -> [bool; 5] {
+    [
+        selection.pokemons.is_active(),
+        selection.spells.is_active(),
+        selection.pokemons.is_active(),
+        selection.fonts.is_active(),
+        selection.pokemons.is_active(),
+    ]
+}
+
+fn run_build_steps(
+    enabled: [bool; 5],
+    manifest: &mut BuildManifest,
+    selection: &Selection,
+    options: BuildOptions,
+) -> BuildReport {
+    let mut report = BuildReport::default();
+    for (enabled, build) in enabled.into_iter().zip(BUILD_STEPS) {
+        if !enabled {
+            continue;
+        }
+        // Stop at the first failure unless asked to keep going
+        if !options.keep_going && !report.is_success() {
+            break;
+        }
+        report.failures.extend(build(manifest, selection, options));
     }
-    if args.bitmap_fonts || args.all {
-        build_bitmap_fonts();
+    report
+}
+
+fn save_manifest(manifest: &BuildManifest) {
+    if let Err(error) = manifest.save(Path::new(MANIFEST_PATH)) {
+        eprintln!("Failed to save the build manifest: {error}");
     }
 }
 
-fn build_pokemon_data() {
+type BuildStep = fn(&mut BuildManifest, &Selection, BuildOptions) -> Vec<BuildFailure>;
+
+fn build_pokemon_data(
+    manifest: &mut BuildManifest,
+    selection: &Selection,
+    options: BuildOptions,
+) -> Vec<BuildFailure> {
     println!("Building pokemon data...");
 
     let pokemon_raw_data_path = Path::new(POKEMON_DATA_RAW_FOLDER_PATH);
-    create_pokemon_data(pokemon_raw_data_path, "assets/data/pokemons");
+    let files = match list_files_in_folder(pokemon_raw_data_path, Some("json")) {
+        Ok(files) => files,
+        Err(source) => {
+            return vec![folder_failure(DataError::Read {
+                path: pokemon_raw_data_path.to_path_buf(),
+                source,
+            })]
+        }
+    };
+    let jobs = files
+        .into_iter()
+        .map(PathBuf::from)
+        .filter(|file| selection.pokemon_name(&file_stem(file)))
+        .map(|source_file| {
+            let file_stem = file_stem(&source_file);
+            let output = format!("{POKEMON_DATA_FOLDER_PATH}/{file_stem}.pd.ron");
+            BuildJob::new(output.clone(), vec![source_file.clone()], move || {
+                create_pokemon_data(&source_file, &output)
+            })
+        })
+        .collect();
+    manifest.run(jobs, options)
 }
 
-fn build_spell_data() {
+fn build_spell_data(
+    manifest: &mut BuildManifest,
+    selection: &Selection,
+    options: BuildOptions,
+) -> Vec<BuildFailure> {
     println!("Building spell data...");
 
-    let pokemon_raw_data_path = Path::new(SPELL_DATA_RAW_FOLDER_PATH);
-    create_spell_data(pokemon_raw_data_path, "assets/data/spells");
+    let spell_raw_data_path = Path::new(SPELL_DATA_RAW_FOLDER_PATH);
+    let files = match list_files_in_folder(spell_raw_data_path, Some("json")) {
+        Ok(files) => files,
+        Err(source) => {
+            return vec![folder_failure(DataError::Read {
+                path: spell_raw_data_path.to_path_buf(),
+                source,
+            })]
+        }
+    };
+    let jobs = files
+        .into_iter()
+        .map(PathBuf::from)
+        .filter(|file| selection.spells.matches([file_stem(file).as_str()]))
+        .map(|source_file| {
+            let file_stem = file_stem(&source_file);
+            let output = format!("{SPELL_DATA_FOLDER_PATH}/{file_stem}.sd.ron");
+            BuildJob::new(output.clone(), vec![source_file.clone()], move || {
+                create_spell_data(&source_file, &output)
+            })
+        })
+        .collect();
+    manifest.run(jobs, options)
 }
 
-fn build_char_animations() {
+fn build_char_animations(
+    manifest: &mut BuildManifest,
+    selection: &Selection,
+    options: BuildOptions,
+) -> Vec<BuildFailure> {
     println!("Building char animations...");
 
     let char_animations_path = Path::new(CHAR_ANIMATION_RAW_FOLDER_PATH);
-    for char_directory in list_directories(char_animations_path).unwrap() {
-        let file_name = char_directory.file_name().unwrap().to_str().unwrap();
-        create_char_animation(&char_directory, &format!("assets/chara/{file_name}.chara"))
-    }
+    let directories = match list_directories(char_animations_path) {
+        Ok(directories) => directories,
+        Err(source) => {
+            return vec![folder_failure(CharAnimationError::Read {
+                path: char_animations_path.to_path_buf(),
+                source,
+            })]
+        }
+    };
+    let jobs = directories
+        .filter(|char_directory| selection.pokemon_id(&file_name(char_directory)))
+        .map(|char_directory| {
+            let file_name = file_name(&char_directory);
+            let output = format!("{CHAR_ANIMATION_FOLDER_PATH}/{file_name}.chara");
+            BuildJob::new(
+                output.clone(),
+                directory_files(&char_directory),
+                move || create_char_animation(&char_directory, &output),
+            )
+        })
+        .collect();
+    manifest.run(jobs, options)
 }
 
-fn build_bitmap_fonts() {
+fn build_bitmap_fonts(
+    manifest: &mut BuildManifest,
+    selection: &Selection,
+    options: BuildOptions,
+) -> Vec<BuildFailure> {
     println!("Building bitmap fonts...");
 
     let fonts_to_load = vec![
@@ -104,9 +346,83 @@ fn build_bitmap_fonts() {
         ("text", UVec2::splat(64 * 29)),
         ("yellow", UVec2::new(96, 10)),
     ];
-    fonts_to_load.into_iter().for_each(|(font, atlas_size)| {
-        let font_dir = format!("{FONT_RAW_FOLDER_PATH}/{font}");
-        let font_dir = Path::new(&font_dir);
-        create_bitmap_font(font_dir, &format!("assets/fonts/{font}.bfn"), atlas_size);
-    });
+    let jobs = fonts_to_load
+        .into_iter()
+        .filter(|(font, _)| selection.fonts.matches([*font]))
+        .map(|(font, atlas_size)| {
+            let font_dir = PathBuf::from(format!("{FONT_RAW_FOLDER_PATH}/{font}"));
+            let output = format!("{FONT_FOLDER_PATH}/{font}.bfn");
+            BuildJob::new(output.clone(), directory_files(&font_dir), move || {
+                create_bitmap_font(&font_dir, &output, atlas_size)
+            })
+        })
+        .collect();
+    manifest.run(jobs, options)
+}
+
+fn build_portraits(
+    manifest: &mut BuildManifest,
+    selection: &Selection,
+    options: BuildOptions,
+) -> Vec<BuildFailure> {
+    println!("Building portraits...");
+
+    // Few species have portraits yet, the folder is optional
+    let portraits_path = Path::new(PORTRAIT_RAW_FOLDER_PATH);
+    if !portraits_path.exists() {
+        println!("No {PORTRAIT_RAW_FOLDER_PATH} folder, skipping the portraits");
+        return vec![];
+    }
+    let directories = match list_directories(portraits_path) {
+        Ok(directories) => directories,
+        Err(source) => {
+            return vec![folder_failure(PortraitError::Read {
+                path: portraits_path.to_path_buf(),
+                source,
+            })]
+        }
+    };
+    let jobs = directories
+        .filter(|portrait_directory| selection.pokemon_id(&file_name(portrait_directory)))
+        .map(|portrait_directory| {
+            let file_name = file_name(&portrait_directory);
+            let output = format!("{PORTRAIT_FOLDER_PATH}/{file_name}.portrait");
+            BuildJob::new(
+                output.clone(),
+                directory_files(&portrait_directory),
+                move || create_portrait(&portrait_directory, &output),
+            )
+        })
+        .collect();
+    manifest.run(jobs, options)
+}
+
+/// A raw assets folder that can't be listed fails the whole step
+fn folder_failure(error: impl Into<AssetError>) -> BuildFailure {
+    let error = error.into();
+    BuildFailure {
+        asset: "raw_assets".to_string(),
+        error,
+    }
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Every file of the directory is an input of the asset built from it
+fn directory_files(directory: &Path) -> Vec<PathBuf> {
+    list_files_in_folder(directory, None)
+        .unwrap_or_default()
+        .into_iter()
+        .map(PathBuf::from)
+        .collect()
 }
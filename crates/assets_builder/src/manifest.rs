@@ -0,0 +1,310 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs, io,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::report::{AssetError, BuildFailure};
+
+/// Bump it when the output of a builder changes, so that every asset is rebuilt
+pub const BUILDER_VERSION: u32 = 6;
+
+pub const MANIFEST_PATH: &str = "assets/.build_manifest.json";
+
+/// The inputs each output was built from, to only rebuild the outputs whose inputs changed
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct BuildManifest {
+    pub outputs: HashMap<String, ManifestEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub builder_version: u32,
+    /// Content hash of each input file
+    pub inputs: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BuildOptions {
+    /// Ignore the manifest and rebuild every output
+    pub force: bool,
+    /// Keep building the other outputs after a failure
+    pub keep_going: bool,
+}
+
+type BuildFn<'a> = Box<dyn Fn() -> Result<(), AssetError> + Send + Sync + 'a>;
+
+/// One output and how to build it from its inputs
+pub struct BuildJob<'a> {
+    pub output: String,
+    pub inputs: Vec<PathBuf>,
+    pub build: BuildFn<'a>,
+}
+
+impl<'a> BuildJob<'a> {
+    pub fn new<E: Into<AssetError>>(
+        output: String,
+        inputs: Vec<PathBuf>,
+        build: impl Fn() -> Result<(), E> + Send + Sync + 'a,
+    ) -> Self {
+        Self {
+            output,
+            inputs,
+            build: Box::new(move || build().map_err(Into::into)),
+        }
+    }
+}
+
+impl BuildManifest {
+    /// A missing or unreadable manifest means everything is rebuilt
+    pub fn load(path: &Path) -> Self {
+        fs::read(path)
+            .ok()
+            .and_then(|content| serde_json::from_slice(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_vec_pretty(self)?;
+        fs::write(path, content)
+    }
+
+    fn is_up_to_date(&self, output: &str, entry: &ManifestEntry) -> bool {
+        Path::new(output).exists() && self.outputs.get(output) == Some(entry)
+    }
+
+    /// Run the jobs whose inputs changed across the cores, or all of them when `force` is set,
+    /// returns the failed ones
+    pub fn run(&mut self, jobs: Vec<BuildJob>, options: BuildOptions) -> Vec<BuildFailure> {
+        let job_count = jobs.len();
+        let failed = AtomicBool::new(false);
+        let results = jobs
+            .into_par_iter()
+            .filter_map(|job| {
+                let entry = ManifestEntry {
+                    builder_version: BUILDER_VERSION,
+                    inputs: hash_inputs(&job.inputs),
+                };
+                if !options.force && self.is_up_to_date(&job.output, &entry) {
+                    return None;
+                }
+                // Don't start any other job once one failed
+                if !options.keep_going && failed.load(Ordering::Relaxed) {
+                    return None;
+                }
+
+                let result = (job.build)();
+                if result.is_err() {
+                    failed.store(true, Ordering::Relaxed);
+                }
+                Some((job.output, entry, result))
+            })
+            .collect::<Vec<_>>();
+
+        let mut built = 0;
+        let mut failures = vec![];
+        for (output, entry, result) in results {
+            match result {
+                Ok(()) => {
+                    built += 1;
+                    self.outputs.insert(output, entry);
+                }
+                Err(error) => {
+                    // Rebuilt on the next run even if its inputs didn't change
+                    self.outputs.remove(&output);
+                    failures.push(BuildFailure {
+                        asset: output,
+                        error,
+                    });
+                }
+            }
+        }
+
+        println!(
+            "{built} built, {} failed, {} up to date or skipped",
+            failures.len(),
+            job_count - built - failures.len()
+        );
+        failures
+    }
+}
+
+/// Unreadable inputs are left out, so that the output is rebuilt once they are readable
+fn hash_inputs(inputs: &[PathBuf]) -> BTreeMap<String, String> {
+    inputs
+        .iter()
+        .filter_map(|input| {
+            let content = fs::read(input).ok()?;
+            Some((
+                input.to_string_lossy().into_owned(),
+                blake3::hash(&content).to_hex().to_string(),
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs, io,
+        path::PathBuf,
+        sync::atomic::{AtomicBool, Ordering},
+    };
+
+    use crate::{data::DataError, report::BuildFailure};
+
+    use super::{BuildJob, BuildManifest, BuildOptions};
+
+    /// A scratch folder with one input and the output built from it
+    struct Fixture {
+        folder: PathBuf,
+        input: PathBuf,
+        output: PathBuf,
+    }
+
+    impl Fixture {
+        fn new(name: &str) -> Self {
+            let folder =
+                std::env::temp_dir().join(format!("manifest_{name}_{}", std::process::id()));
+            let _ = fs::remove_dir_all(&folder);
+            fs::create_dir_all(&folder).unwrap();
+            let input = folder.join("input.json");
+            fs::write(&input, "first").unwrap();
+
+            Self {
+                input,
+                output: folder.join("output.ron"),
+                folder,
+            }
+        }
+
+        fn output_key(&self) -> String {
+            self.output.to_string_lossy().into_owned()
+        }
+
+        /// Run the job of the output, returns whether it was built and the failures
+        fn run(
+            &self,
+            manifest: &mut BuildManifest,
+            options: BuildOptions,
+            succeed: bool,
+        ) -> (bool, Vec<BuildFailure>) {
+            let built = AtomicBool::new(false);
+            let job = BuildJob::new(self.output_key(), vec![self.input.clone()], || {
+                built.store(true, Ordering::Relaxed);
+                if !succeed {
+                    return Err(DataError::Write(io::Error::other("failed build")));
+                }
+                fs::write(&self.output, "built").map_err(DataError::Write)
+            });
+            let failures = manifest.run(vec![job], options);
+            (built.into_inner(), failures)
+        }
+
+        fn build(&self, manifest: &mut BuildManifest) -> bool {
+            let (built, failures) = self.run(manifest, BuildOptions::default(), true);
+            assert!(failures.is_empty(), "{failures:?}");
+            built
+        }
+    }
+
+    impl Drop for Fixture {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.folder);
+        }
+    }
+
+    #[test]
+    fn skips_an_unchanged_input() {
+        let fixture = Fixture::new("unchanged");
+        let mut manifest = BuildManifest::default();
+
+        assert!(fixture.build(&mut manifest));
+        assert!(!fixture.build(&mut manifest));
+    }
+
+    #[test]
+    fn rebuilds_a_changed_input() {
+        let fixture = Fixture::new("changed");
+        let mut manifest = BuildManifest::default();
+        fixture.build(&mut manifest);
+
+        fs::write(&fixture.input, "second").unwrap();
+        assert!(fixture.build(&mut manifest));
+    }
+
+    #[test]
+    fn rebuilds_a_removed_input() {
+        let fixture = Fixture::new("removed");
+        let mut manifest = BuildManifest::default();
+        fixture.build(&mut manifest);
+
+        fs::remove_file(&fixture.input).unwrap();
+        assert!(fixture.build(&mut manifest));
+    }
+
+    #[test]
+    fn rebuilds_another_builder_version() {
+        let fixture = Fixture::new("version");
+        let mut manifest = BuildManifest::default();
+        fixture.build(&mut manifest);
+
+        for entry in manifest.outputs.values_mut() {
+            entry.builder_version -= 1;
+        }
+        assert!(fixture.build(&mut manifest));
+    }
+
+    #[test]
+    fn rebuilds_a_deleted_output() {
+        let fixture = Fixture::new("deleted");
+        let mut manifest = BuildManifest::default();
+        fixture.build(&mut manifest);
+
+        fs::remove_file(&fixture.output).unwrap();
+        assert!(fixture.build(&mut manifest));
+    }
+
+    #[test]
+    fn force_rebuilds_an_up_to_date_output() {
+        let fixture = Fixture::new("force");
+        let mut manifest = BuildManifest::default();
+        fixture.build(&mut manifest);
+
+        let options = BuildOptions {
+            force: true,
+            ..Default::default()
+        };
+        let (built, failures) = fixture.run(&mut manifest, options, true);
+        assert!(built);
+        assert!(failures.is_empty());
+    }
+
+    #[test]
+    fn drops_a_failed_job_from_the_manifest() {
+        let fixture = Fixture::new("failed");
+        let mut manifest = BuildManifest::default();
+        fixture.build(&mut manifest);
+
+        let options = BuildOptions {
+            force: true,
+            ..Default::default()
+        };
+        let (built, failures) = fixture.run(&mut manifest, options, false);
+        assert!(built);
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].asset, fixture.output_key());
+        assert!(!manifest.outputs.contains_key(&fixture.output_key()));
+
+        // The output left from the previous build is not trusted anymore, even if its input
+        // didn't change
+        assert!(fixture.build(&mut manifest));
+    }
+}
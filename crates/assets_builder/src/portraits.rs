@@ -0,0 +1,117 @@
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use image::{GenericImage, ImageError, RgbaImage};
+use portrait::{emotion::Emotion, file::PortraitFile};
+use strum::IntoEnumIterator;
+use thiserror::Error;
+
+use crate::utils::{encode_png, list_files_in_folder, write_atomically};
+
+/// Portraits per row of the built texture
+const COLUMNS: u32 = 5;
+
+#[derive(Debug, Error)]
+pub enum PortraitError {
+    #[error("failed to read {path}: {source}")]
+    Read { path: PathBuf, source: io::Error },
+    #[error("failed to open {path}: {source}")]
+    Texture { path: PathBuf, source: ImageError },
+    #[error("{0} is not named after an emotion")]
+    UnknownEmotion(PathBuf),
+    #[error("{path} is {width}x{height} but the portraits are {expected}x{expected}")]
+    Size {
+        path: PathBuf,
+        expected: u32,
+        width: u32,
+        height: u32,
+    },
+    #[error("no {} portrait", Emotion::Normal)]
+    MissingNormal,
+    #[error("failed to compress the portraits: {0}")]
+    Compress(#[source] ImageError),
+    #[error("failed to write the portrait: {0}")]
+    Write(#[source] io::Error),
+}
+
+/// Pack the `{Emotion}.png` faces of the portrait folder into a single texture
+pub fn create_portrait(
+    source_directory: &Path,
+    output_filename: &str,
+) -> Result<(), PortraitError> {
+    println!("Creating {output_filename}");
+    let output_path = Path::new(output_filename);
+    fs::create_dir_all(output_path.parent().unwrap()).map_err(PortraitError::Write)?;
+
+    let files = list_files_in_folder(source_directory, Some("png")).map_err(|source| {
+        PortraitError::Read {
+            path: source_directory.to_path_buf(),
+            source,
+        }
+    })?;
+
+    let mut faces = HashMap::new();
+    for file in files.into_iter().map(PathBuf::from) {
+        let file_stem = file
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or_default();
+        // The right facing variants (`Normal^.png`) are not used, the faces are flipped instead
+        if file_stem.ends_with('^') {
+            continue;
+        }
+        let emotion = Emotion::from_str(file_stem)
+            .map_err(|_| PortraitError::UnknownEmotion(file.clone()))?;
+        let face = image::open(&file)
+            .map_err(|source| PortraitError::Texture {
+                path: file.clone(),
+                source,
+            })?
+            .to_rgba8();
+        faces.insert(emotion, (file, face));
+    }
+
+    let Some((_, normal)) = faces.get(&Emotion::Normal) else {
+        return Err(PortraitError::MissingNormal);
+    };
+    let portrait_size = normal.width();
+
+    // Laid out in the order of the emotions so that the builds are reproducible
+    let faces = Emotion::iter()
+        .filter_map(|emotion| faces.remove(&emotion).map(|face| (emotion, face)))
+        .collect::<Vec<_>>();
+    let columns = COLUMNS.min(faces.len() as u32);
+    let rows = (faces.len() as u32).div_ceil(columns);
+    let mut texture = RgbaImage::new(portrait_size * columns, portrait_size * rows);
+    let mut emotions = HashMap::new();
+
+    for (index, (emotion, (path, face))) in faces.into_iter().enumerate() {
+        if face.width() != portrait_size || face.height() != portrait_size {
+            return Err(PortraitError::Size {
+                path,
+                expected: portrait_size,
+                width: face.width(),
+                height: face.height(),
+            });
+        }
+        let (column, row) = (index as u32 % columns, index as u32 / columns);
+        texture
+            .copy_from(&face, column * portrait_size, row * portrait_size)
+            .expect("the texture holds every portrait");
+        emotions.insert(emotion, index);
+    }
+
+    let portrait = PortraitFile {
+        texture: encode_png(&texture).map_err(PortraitError::Compress)?,
+        portrait_size,
+        columns,
+        rows,
+        emotions,
+    };
+    write_atomically(output_path, |portrait_file| portrait.save(portrait_file))
+        .map_err(PortraitError::Write)
+}
@@ -0,0 +1,48 @@
+use thiserror::Error;
+
+use crate::{
+    bitmap_fonts::BitmapFontError, char_animations::CharAnimationError, data::DataError,
+    portraits::PortraitError,
+};
+
+#[derive(Debug, Error)]
+pub enum AssetError {
+    #[error(transparent)]
+    CharAnimation(#[from] CharAnimationError),
+    #[error(transparent)]
+    BitmapFont(#[from] BitmapFontError),
+    #[error(transparent)]
+    Data(#[from] DataError),
+    #[error(transparent)]
+    Portrait(#[from] PortraitError),
+}
+
+#[derive(Debug)]
+pub struct BuildFailure {
+    pub asset: String,
+    pub error: AssetError,
+}
+
+/// The assets that failed to build, printed once the build is over
+#[derive(Debug, Default)]
+pub struct BuildReport {
+    pub failures: Vec<BuildFailure>,
+}
+
+impl BuildReport {
+    pub fn is_success(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    pub fn print(&self) {
+        if self.is_success() {
+            println!("All assets built");
+            return;
+        }
+
+        eprintln!("{} assets failed to build:", self.failures.len());
+        for failure in self.failures.iter() {
+            eprintln!("  {}: {}", failure.asset, failure.error);
+        }
+    }
+}
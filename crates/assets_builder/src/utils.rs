@@ -1,8 +1,11 @@
 use std::{
-    fs, io,
+    fs::{self, File},
+    io::{self, Cursor},
     path::{Path, PathBuf},
 };
 
+use image::{codecs::png::PngEncoder, ColorType, ImageEncoder, ImageError, RgbaImage};
+
 pub fn list_files_in_folder(
     folder_path: &Path,
     file_ext: Option<&str>,
@@ -45,3 +48,58 @@ pub fn list_directories(path: &Path) -> io::Result<impl Iterator<Item = PathBuf>
         None
     }))
 }
+
+/// The `ID<TAB>name` lines of the pokemon conversion table, with the malformed lines as errors
+pub fn parse_pokemon_conversions(
+    conversions: &str,
+) -> impl Iterator<Item = Result<(u32, &str), String>> {
+    conversions
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let (id, name) = line
+                .split_once('\t')
+                .ok_or_else(|| format!("malformed line `{line}`"))?;
+            let id = id
+                .trim()
+                .parse::<u32>()
+                .map_err(|_| format!("invalid ID in `{line}`"))?;
+            Ok((id, name.trim()))
+        })
+}
+
+/// Write the output next to its final path then move it there, so that the game never loads a
+/// half written asset
+pub fn write_atomically(
+    output: &Path,
+    write: impl FnOnce(&mut File) -> io::Result<()>,
+) -> io::Result<()> {
+    let mut temporary = output.as_os_str().to_owned();
+    temporary.push(".tmp");
+    let temporary = PathBuf::from(temporary);
+
+    let result = File::create(&temporary).and_then(|mut file| {
+        write(&mut file)?;
+        file.sync_all()
+    });
+    match result {
+        Ok(()) => fs::rename(&temporary, output),
+        Err(error) => {
+            let _ = fs::remove_file(&temporary);
+            Err(error)
+        }
+    }
+}
+
+pub fn encode_png(image: &RgbaImage) -> Result<Vec<u8>, ImageError> {
+    let mut bytes: Vec<u8> = Vec::new();
+    let mut cursor = Cursor::new(&mut bytes);
+    let encoder = PngEncoder::new(&mut cursor);
+    encoder.write_image(
+        image.as_raw(),
+        image.width(),
+        image.height(),
+        ColorType::Rgba8.into(),
+    )?;
+    Ok(bytes)
+}
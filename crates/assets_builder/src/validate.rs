@@ -0,0 +1,226 @@
+use std::{
+    collections::BTreeSet,
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use bitmap_font::bfn::Font;
+use char_animation::file::CharAnimationFile;
+use portrait::file::PortraitFile;
+use serde_json::Value;
+use spell_data::SpellData;
+
+use crate::{
+    char_animations::required_textures,
+    data::read_json,
+    utils::{list_directories, list_files_in_folder, parse_pokemon_conversions},
+    CHAR_ANIMATION_FOLDER_PATH, CHAR_ANIMATION_RAW_FOLDER_PATH, FONT_FOLDER_PATH,
+    POKEMON_CONVERSION_PATH, POKEMON_DATA_FOLDER_PATH, PORTRAIT_FOLDER_PATH,
+    SPELL_DATA_FOLDER_PATH, SPELL_DATA_RAW_FOLDER_PATH, VISUAL_EFFECTS_FOLDER_PATH,
+};
+
+#[derive(Debug)]
+pub struct ValidationIssue {
+    pub asset: String,
+    pub reason: String,
+}
+
+/// Problems found in the asset set, nothing is written while looking for them
+#[derive(Debug, Default)]
+struct Validation {
+    issues: Vec<ValidationIssue>,
+}
+
+impl Validation {
+    fn report(&mut self, asset: impl Display, reason: impl Display) {
+        self.issues.push(ValidationIssue {
+            asset: asset.to_string(),
+            reason: reason.to_string(),
+        });
+    }
+
+    /// Every `-Anim`, `-Offsets` and `-Shadow` texture referenced by an `AnimData.xml` exists,
+    /// parsing it also checks that every `CopyOf` resolves
+    fn sprite_folders(&mut self) {
+        let sprites_path = Path::new(CHAR_ANIMATION_RAW_FOLDER_PATH);
+        let directories = match list_directories(sprites_path) {
+            Ok(directories) => directories,
+            Err(error) => return self.report(sprites_path.display(), error),
+        };
+
+        for directory in directories {
+            match required_textures(&directory) {
+                Ok(textures) => {
+                    for texture in textures.iter().filter(|texture| !texture.is_file()) {
+                        self.report(texture.display(), "referenced by AnimData.xml but missing");
+                    }
+                }
+                Err(error) => self.report(directory.display(), error),
+            }
+        }
+    }
+
+    /// Every pokemon ID has both its data and its char animation built
+    fn pokemon_assets(&mut self) {
+        let conversions = match fs::read_to_string(POKEMON_CONVERSION_PATH) {
+            Ok(conversions) => conversions,
+            Err(error) => return self.report(POKEMON_CONVERSION_PATH, error),
+        };
+
+        for conversion in parse_pokemon_conversions(&conversions) {
+            let (id, name) = match conversion {
+                Ok(conversion) => conversion,
+                Err(reason) => {
+                    self.report(POKEMON_CONVERSION_PATH, reason);
+                    continue;
+                }
+            };
+
+            for output in [
+                PathBuf::from(format!("{POKEMON_DATA_FOLDER_PATH}/{name}.pd.ron")),
+                PathBuf::from(format!("{CHAR_ANIMATION_FOLDER_PATH}/{id:04}.chara")),
+            ] {
+                if !output.is_file() {
+                    self.report(
+                        output.display(),
+                        format!("missing for pokemon {id} ({name})"),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Every visual effect a spell plays exists in the visual effects folder
+    fn spell_visual_effects(&mut self) {
+        let visual_effects = match list_files_in_folder(Path::new(VISUAL_EFFECTS_FOLDER_PATH), None)
+        {
+            Ok(files) => files
+                .iter()
+                .filter_map(|file| {
+                    let file_name = Path::new(file).file_name()?.to_str()?;
+                    Some(file_name.split('.').next()?.to_string())
+                })
+                .collect::<BTreeSet<_>>(),
+            Err(error) => return self.report(VISUAL_EFFECTS_FOLDER_PATH, error),
+        };
+
+        let spells = match list_files_in_folder(Path::new(SPELL_DATA_RAW_FOLDER_PATH), Some("json"))
+        {
+            Ok(spells) => spells,
+            Err(error) => return self.report(SPELL_DATA_RAW_FOLDER_PATH, error),
+        };
+
+        for spell in spells {
+            let content = match read_json(Path::new(&spell)) {
+                Ok(content) => content,
+                Err(error) => {
+                    self.report(&spell, error);
+                    continue;
+                }
+            };
+            let value = match serde_json::from_slice::<Value>(&content) {
+                Ok(value) => value,
+                Err(error) => {
+                    self.report(&spell, error);
+                    continue;
+                }
+            };
+
+            let mut names = BTreeSet::new();
+            collect_anim_indices(&value, &mut names);
+            for name in names.difference(&visual_effects) {
+                self.report(&spell, format!("unknown visual effect `{name}`"));
+            }
+        }
+    }
+
+    /// The built files decode with the current file formats
+    fn built_files(&mut self) {
+        self.decode_all(CHAR_ANIMATION_FOLDER_PATH, "chara", |buffer| {
+            CharAnimationFile::load(buffer).map(|_| ())
+        });
+        self.decode_all(FONT_FOLDER_PATH, "bfn", |buffer| {
+            Font::load(buffer).map(|_| ())
+        });
+        self.decode_all(SPELL_DATA_FOLDER_PATH, "ron", |buffer| {
+            SpellData::load(buffer).map(|_| ())
+        });
+        // The portraits are optional
+        if Path::new(PORTRAIT_FOLDER_PATH).exists() {
+            self.decode_all(PORTRAIT_FOLDER_PATH, "portrait", |buffer| {
+                PortraitFile::load(buffer).map(|_| ())
+            });
+        }
+    }
+
+    fn decode_all<E: Display>(
+        &mut self,
+        folder: &str,
+        extension: &str,
+        decode: impl Fn(&[u8]) -> Result<(), E>,
+    ) {
+        let files = match list_files_in_folder(Path::new(folder), Some(extension)) {
+            Ok(files) => files,
+            Err(error) => return self.report(folder, error),
+        };
+
+        for file in files {
+            match fs::read(&file) {
+                Ok(buffer) => {
+                    if let Err(error) = decode(&buffer) {
+                        self.report(&file, format!("failed to decode: {error}"));
+                    }
+                }
+                Err(error) => self.report(&file, error),
+            }
+        }
+    }
+}
+
+/// The visual effects are referenced by their `AnimIndex` anywhere in the spell data
+fn collect_anim_indices(value: &Value, names: &mut BTreeSet<String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                match value {
+                    Value::String(name) if key == "AnimIndex" && !name.is_empty() => {
+                        names.insert(name.clone());
+                    }
+                    _ => collect_anim_indices(value, names),
+                }
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                collect_anim_indices(value, names);
+            }
+        }
+        _ => {}
+    }
+}
+
+pub fn validate() -> ExitCode {
+    let mut validation = Validation::default();
+
+    println!("Checking sprite folders...");
+    validation.sprite_folders();
+    println!("Checking pokemon assets...");
+    validation.pokemon_assets();
+    println!("Checking spell visual effects...");
+    validation.spell_visual_effects();
+    println!("Checking built files...");
+    validation.built_files();
+
+    if validation.issues.is_empty() {
+        println!("All assets are valid");
+        return ExitCode::SUCCESS;
+    }
+
+    for issue in validation.issues.iter() {
+        eprintln!("  {}: {}", issue.asset, issue.reason);
+    }
+    eprintln!("{} issues found", validation.issues.len());
+    ExitCode::FAILURE
+}
@@ -0,0 +1,131 @@
+use std::{collections::BTreeSet, env, path::Path, sync::mpsc, time::Duration};
+
+use glob::Pattern;
+use notify::{Event, RecursiveMode, Watcher};
+
+use crate::{
+    filter::{AssetFilter, Selection},
+    CHAR_ANIMATION_RAW_FOLDER_PATH, FONT_RAW_FOLDER_PATH, POKEMON_DATA_RAW_FOLDER_PATH,
+    PORTRAIT_RAW_FOLDER_PATH, SPELL_DATA_RAW_FOLDER_PATH,
+};
+
+const RAW_ASSETS_FOLDER_PATH: &str = "raw_assets";
+const SETTLE_DELAY: Duration = Duration::from_millis(500);
+
+/// The assets whose raw files were added, modified or removed
+#[derive(Debug, Default)]
+pub struct RawChanges {
+    pokemon_ids: BTreeSet<String>,
+    pokemon_names: BTreeSet<String>,
+    fonts: BTreeSet<String>,
+    spells: BTreeSet<String>,
+}
+
+impl RawChanges {
+    fn add(&mut self, path: &Path) {
+        let first_component = |folder: &str| {
+            let relative = path.strip_prefix(folder).ok()?;
+            let component = relative.components().next()?;
+            Some(component.as_os_str().to_string_lossy().into_owned())
+        };
+        let file_stem = |folder: &str| {
+            path.strip_prefix(folder).ok()?;
+            Some(path.file_stem()?.to_string_lossy().into_owned())
+        };
+
+        if let Some(id) = first_component(CHAR_ANIMATION_RAW_FOLDER_PATH) {
+            self.pokemon_ids.insert(id);
+        } else if let Some(id) = first_component(PORTRAIT_RAW_FOLDER_PATH) {
+            self.pokemon_ids.insert(id);
+        } else if let Some(name) = file_stem(POKEMON_DATA_RAW_FOLDER_PATH) {
+            self.pokemon_names.insert(name);
+        } else if let Some(font) = first_component(FONT_RAW_FOLDER_PATH) {
+            self.fonts.insert(font);
+        } else if let Some(spell) = file_stem(SPELL_DATA_RAW_FOLDER_PATH) {
+            self.spells.insert(spell);
+        }
+    }
+
+    fn add_event(&mut self, event: notify::Result<Event>, current_dir: &Path) {
+        let event = match event {
+            // Reading the raw files, like the builder does, changes nothing
+            Ok(event) if event.kind.is_access() => return,
+            Ok(event) => event,
+            Err(error) => {
+                eprintln!("Watch error: {error}");
+                return;
+            }
+        };
+        for path in event.paths {
+            self.add(path.strip_prefix(current_dir).unwrap_or(&path));
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pokemon_ids.is_empty()
+            && self.pokemon_names.is_empty()
+            && self.fonts.is_empty()
+            && self.spells.is_empty()
+    }
+
+    /// The changed assets among the selected ones
+    pub fn selection(&self, selected: &Selection) -> Selection {
+        let pokemons = self
+            .pokemon_ids
+            .iter()
+            .filter(|id| selected.pokemon_id(id))
+            .chain(
+                self.pokemon_names
+                    .iter()
+                    .filter(|name| selected.pokemon_name(name)),
+            );
+        let fonts = self
+            .fonts
+            .iter()
+            .filter(|font| selected.fonts.matches([font.as_str()]));
+        let spells = self
+            .spells
+            .iter()
+            .filter(|spell| selected.spells.matches([spell.as_str()]));
+
+        Selection::new(
+            exact_filter(pokemons),
+            exact_filter(fonts),
+            exact_filter(spells),
+        )
+    }
+}
+
+fn exact_filter<'a>(names: impl Iterator<Item = &'a String>) -> AssetFilter {
+    AssetFilter::new(
+        names
+            .map(|name| Pattern::new(&Pattern::escape(name)).expect("An escaped pattern is valid"))
+            .collect(),
+    )
+}
+
+/// Watch the raw assets and call `rebuild` with the changes, once the files stopped changing
+pub fn watch(mut rebuild: impl FnMut(&RawChanges)) -> ! {
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender).expect("Failed to create the watcher");
+    watcher
+        .watch(Path::new(RAW_ASSETS_FOLDER_PATH), RecursiveMode::Recursive)
+        .unwrap_or_else(|error| panic!("Failed to watch {RAW_ASSETS_FOLDER_PATH}: {error}"));
+    let current_dir = env::current_dir().expect("The current directory is readable");
+
+    println!("Watching {RAW_ASSETS_FOLDER_PATH} for changes...");
+    loop {
+        let mut changes = RawChanges::default();
+        let event = receiver.recv().expect("The watcher is alive");
+        changes.add_event(event, &current_dir);
+
+        // Wait for the editor or the version control to be done writing
+        while let Ok(event) = receiver.recv_timeout(SETTLE_DELAY) {
+            changes.add_event(event, &current_dir);
+        }
+
+        if !changes.is_empty() {
+            rebuild(&changes);
+        }
+    }
+}
@@ -4,12 +4,26 @@ use bevy::{
     prelude::*,
     reflect::TypePath,
 };
+use bincode::error::DecodeError;
+use common::asset_header::{AssetHeader, AssetHeaderError};
 use serde::{Deserialize, Serialize};
 use std::{
     collections::HashMap,
     fs::File,
     io::{self, Write},
 };
+use thiserror::Error;
+
+/// Bump the version when the layout of [`Font`] changes
+pub const FONT_HEADER: AssetHeader = AssetHeader::new(b"BFNT", 1);
+
+#[derive(Debug, Error)]
+pub enum FontFileError {
+    #[error(transparent)]
+    Header(#[from] AssetHeaderError),
+    #[error("could not decode the font, rebuild the assets: {0}")]
+    Decode(#[from] DecodeError),
+}
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct Font {
@@ -44,13 +58,14 @@ impl Font {
     pub fn save(&self, file: &mut File) -> Result<(), io::Error> {
         let buffer = bincode::serde::encode_to_vec(self, bincode::config::standard())
             .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
-        file.write_all(&buffer)?;
+        file.write_all(&FONT_HEADER.write(&buffer))?;
         Ok(())
     }
 
-    pub fn load(buffer: &[u8]) -> Result<Self, bincode::error::DecodeError> {
+    pub fn load(buffer: &[u8]) -> Result<Self, FontFileError> {
+        let payload = FONT_HEADER.read(buffer)?;
         let (font_sheet, _): (Font, usize) =
-            bincode::serde::decode_from_slice(buffer, bincode::config::standard()).unwrap();
+            bincode::serde::decode_from_slice(payload, bincode::config::standard())?;
         Ok(font_sheet)
     }
 }
@@ -11,7 +11,7 @@ use bevy::{
     utils::BoxedFuture,
 };
 use bevy_inspector_egui::bevy_egui::egui;
-use bincode::error::DecodeError;
+use image::ImageError;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -36,8 +36,10 @@ pub enum BitmapFontLoaderError {
     /// An [IO](std::io) Error
     #[error("Could not load asset: {0}")]
     Io(#[from] std::io::Error),
-    #[error("Could not parse the asset {0}")]
-    DecodeError(#[from] DecodeError),
+    #[error("Could not parse the asset: {0}")]
+    File(#[from] bfn::FontFileError),
+    #[error("Could not decompress the font texture: {0}")]
+    Texture(#[from] ImageError),
 }
 
 impl AssetLoader for BitmapFontLoader {
@@ -82,9 +84,7 @@ impl AssetLoader for BitmapFontLoader {
                 glyph_uvs.insert(glyph.code_point, glyph_uv);
             }
 
-            let texture_buffer = image::load_from_memory(&font.texture)
-                .expect("Failed to decompress font bitmap texture")
-                .to_rgba8();
+            let texture_buffer = image::load_from_memory(&font.texture)?.to_rgba8();
 
             let texture = Image::new(
                 Extent3d {
@@ -6,12 +6,26 @@ use std::{
 
 use bevy::{
     ecs::component::Component,
-    math::{IVec2, Vec2},
+    math::{IVec2, URect, UVec2, Vec2},
 };
+use bincode::error::DecodeError;
+use common::asset_header::{AssetHeader, AssetHeaderError};
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 use crate::{anim_key::AnimKey, orientation::Orientation};
 
+/// Bump the version when the layout of [`CharAnimationFile`] changes
+pub const CHAR_ANIMATION_HEADER: AssetHeader = AssetHeader::new(b"CHRA", 3);
+
+#[derive(Debug, Error)]
+pub enum CharAnimationFileError {
+    #[error(transparent)]
+    Header(#[from] AssetHeaderError),
+    #[error("could not decode the char animation, rebuild the assets: {0}")]
+    Decode(#[from] DecodeError),
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Default)]
 pub struct IVec2Serialized {
     x: i32,
@@ -30,6 +44,32 @@ impl From<IVec2> for IVec2Serialized {
     }
 }
 
+/// Where a frame is in its atlas page
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy, Default)]
+pub struct FrameRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl From<FrameRect> for URect {
+    fn from(rect: FrameRect) -> Self {
+        URect::new(rect.x, rect.y, rect.x + rect.width, rect.y + rect.height)
+    }
+}
+
+impl From<URect> for FrameRect {
+    fn from(rect: URect) -> Self {
+        FrameRect {
+            x: rect.min.x,
+            y: rect.min.y,
+            width: rect.width(),
+            height: rect.height(),
+        }
+    }
+}
+
 #[derive(Component, Default, Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct CharAnimationOffsets {
     pub body: Vec2,  // Green
@@ -40,11 +80,18 @@ pub struct CharAnimationOffsets {
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct CharAnimationFileEntry {
-    pub texture: Vec<u8>,
+    /// Index of the atlas page holding the frames
+    pub page: usize,
+    /// The frames rects in the page, row by row with one row per orientation
+    pub frames: Vec<FrameRect>,
+    /// Position of each frame in its uncropped `frame_width`x`frame_height` frame, the
+    /// transparent margins are cropped by the builder
+    pub crop_offsets: Vec<UVec2>,
     pub index: usize,
     pub frame_width: u32,
     pub frame_height: u32,
     pub durations: Vec<u32>,
+    /// The sheet has a single row of frames, shown for every orientation
     pub is_single_orientation: bool,
     pub rush_frame: Option<usize>,
     pub hit_frame: Option<usize>,
@@ -57,6 +104,8 @@ pub struct CharAnimationFileEntry {
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct CharAnimationFile {
+    /// PNG encoded atlas pages shared by the animations
+    pub pages: Vec<Vec<u8>>,
     pub anim: HashMap<AnimKey, CharAnimationFileEntry>,
 }
 
@@ -64,13 +113,14 @@ impl CharAnimationFile {
     pub fn save(&self, file: &mut File) -> Result<(), io::Error> {
         let buffer = bincode::serde::encode_to_vec(self, bincode::config::standard())
             .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
-        file.write_all(&buffer)?;
+        file.write_all(&CHAR_ANIMATION_HEADER.write(&buffer))?;
         Ok(())
     }
 
-    pub fn load(buffer: &[u8]) -> Result<Self, bincode::error::DecodeError> {
-        let (font_sheet, _): (CharAnimationFile, usize) =
-            bincode::serde::decode_from_slice(buffer, bincode::config::standard()).unwrap();
-        Ok(font_sheet)
+    pub fn load(buffer: &[u8]) -> Result<Self, CharAnimationFileError> {
+        let payload = CHAR_ANIMATION_HEADER.read(buffer)?;
+        let (char_animation, _): (CharAnimationFile, usize) =
+            bincode::serde::decode_from_slice(payload, bincode::config::standard())?;
+        Ok(char_animation)
     }
 }
@@ -6,18 +6,18 @@ use bevy::{
     asset::{
         io::Reader, Asset, AssetApp, AssetLoader, AsyncReadExt, Handle, LoadContext, LoadedAsset,
     },
-    math::{UVec2, Vec2},
+    math::{URect, UVec2, Vec2},
     reflect::TypePath,
     render::{
         render_asset::RenderAssetUsages,
         render_resource::{Extent3d, TextureDimension, TextureFormat},
         texture::Image,
     },
-    sprite::TextureAtlasLayout,
+    sprite::{Anchor, TextureAtlasLayout},
     utils::BoxedFuture,
 };
-use bincode::error::DecodeError;
-use file::{CharAnimationFile, CharAnimationOffsets};
+use file::{CharAnimationFile, CharAnimationFileError, CharAnimationOffsets};
+use image::ImageError;
 use strum::IntoEnumIterator;
 use thiserror::Error;
 
@@ -39,6 +39,9 @@ pub struct CharAnimationData {
     // Texture / Atlas
     pub texture: Handle<Image>,
     pub atlas_layout: Handle<TextureAtlasLayout>,
+    /// Anchor of each atlas frame, so that the cropped frames are drawn where the full frames
+    /// would be
+    pub frame_anchors: Vec<Anchor>,
 
     // Frames info
     pub index: usize,
@@ -69,8 +72,12 @@ pub enum CharAnimationLoaderError {
     /// An [IO](std::io) Error
     #[error("Could not load asset: {0}")]
     Io(#[from] std::io::Error),
-    #[error("Could not parse the asset {0}")]
-    DecodeError(#[from] DecodeError),
+    #[error("Could not parse the asset: {0}")]
+    File(#[from] CharAnimationFileError),
+    #[error("Could not decompress atlas page {page}: {source}")]
+    Texture { page: usize, source: ImageError },
+    #[error("The {anim_key} frames are on atlas page {page}, which doesn't exist")]
+    MissingPage { anim_key: AnimKey, page: usize },
 }
 
 impl AssetLoader for CharAnimationLoader {
@@ -89,18 +96,21 @@ impl AssetLoader for CharAnimationLoader {
             reader.read_to_end(&mut bytes).await?;
             let char_animation_file = CharAnimationFile::load(&bytes)?;
 
-            let char_animations = char_animation_file
-                .anim
+            // One texture per atlas page, shared by the animations packed on it
+            let pages = char_animation_file
+                .pages
                 .iter()
-                .map(|(anim_key, char_animation_entry)| {
-                    let texture_buffer = image::load_from_memory(&char_animation_entry.texture)
-                        .expect("Failed to decompress char animation texture")
+                .enumerate()
+                .map(|(page, page_bytes)| {
+                    let texture_buffer = image::load_from_memory(page_bytes)
+                        .map_err(|source| CharAnimationLoaderError::Texture { page, source })?
                         .to_rgba8();
+                    let size = UVec2::new(texture_buffer.width(), texture_buffer.height());
 
                     let texture = Image::new(
                         Extent3d {
-                            width: texture_buffer.width(),
-                            height: texture_buffer.height(),
+                            width: size.x,
+                            height: size.y,
                             depth_or_array_layers: 1,
                         },
                         TextureDimension::D2,
@@ -110,27 +120,48 @@ impl AssetLoader for CharAnimationLoader {
                         RenderAssetUsages::RENDER_WORLD,
                     );
 
-                    let texture_label = format!("{}_texture.png", anim_key);
+                    let texture_label = format!("page_{}_texture.png", page);
                     let texture_handle = load_context
                         .add_loaded_labeled_asset(texture_label, LoadedAsset::from(texture));
+                    Ok((texture_handle, size))
+                })
+                .collect::<Result<Vec<_>, CharAnimationLoaderError>>()?;
 
-                    let tile_size = UVec2::new(
-                        char_animation_entry.frame_width,
-                        char_animation_entry.frame_height,
-                    );
+            let char_animations = char_animation_file
+                .anim
+                .iter()
+                .map(|(anim_key, char_animation_entry)| {
+                    let Some((texture_handle, page_size)) = pages.get(char_animation_entry.page)
+                    else {
+                        return Err(CharAnimationLoaderError::MissingPage {
+                            anim_key: *anim_key,
+                            page: char_animation_entry.page,
+                        });
+                    };
 
+                    // The sheets without orientations have a single row, it is repeated so
+                    // that the atlas indices are those of a sheet with every orientation
                     let rows = match char_animation_entry.is_single_orientation {
-                        false => Orientation::iter().len(),
-                        true => 1,
+                        true => Orientation::iter().len(),
+                        false => 1,
                     };
-
-                    let atlas_layout = TextureAtlasLayout::from_grid(
-                        tile_size,
-                        char_animation_entry.durations.len() as u32,
-                        rows as u32,
-                        None,
-                        None,
+                    let frame_size = UVec2::new(
+                        char_animation_entry.frame_width,
+                        char_animation_entry.frame_height,
                     );
+                    let mut atlas_layout = TextureAtlasLayout::new_empty(*page_size);
+                    let mut frame_anchors = vec![];
+                    for _ in 0..rows {
+                        for (frame, crop_offset) in char_animation_entry
+                            .frames
+                            .iter()
+                            .zip(char_animation_entry.crop_offsets.iter())
+                        {
+                            let rect: URect = (*frame).into();
+                            atlas_layout.add_texture(rect);
+                            frame_anchors.push(frame_anchor(rect.size(), *crop_offset, frame_size));
+                        }
+                    }
 
                     let atlas_layout_label = format!("{}_atlas_layout_label", anim_key);
                     let atlas_layout_handle = load_context.add_loaded_labeled_asset(
@@ -140,8 +171,9 @@ impl AssetLoader for CharAnimationLoader {
 
                     // TODO: find a better way than cloning the char_animation_entry
                     let data = CharAnimationData {
-                        texture: texture_handle,
+                        texture: texture_handle.clone(),
                         atlas_layout: atlas_layout_handle,
+                        frame_anchors,
                         index: char_animation_entry.index,
                         frame_width: char_animation_entry.frame_width,
                         frame_height: char_animation_entry.frame_height,
@@ -152,9 +184,9 @@ impl AssetLoader for CharAnimationLoader {
                         shadow_offsets: char_animation_entry.shadow_offsets.to_owned(),
                         offsets: char_animation_entry.offsets.to_owned(),
                     };
-                    (*anim_key, data)
+                    Ok((*anim_key, data))
                 })
-                .collect::<HashMap<_, _>>();
+                .collect::<Result<HashMap<_, _>, CharAnimationLoaderError>>()?;
 
             Ok(CharAnimation {
                 anim: char_animations,
@@ -166,3 +198,45 @@ impl AssetLoader for CharAnimationLoader {
         &["chara"]
     }
 }
+
+/// The anchor that puts the center of the uncropped frame on the sprite position
+fn frame_anchor(size: UVec2, crop_offset: UVec2, frame_size: UVec2) -> Anchor {
+    let size = size.as_vec2();
+    // From the center of the cropped frame to the center of the full frame, y down
+    let to_center = frame_size.as_vec2() / 2. - crop_offset.as_vec2() - size / 2.;
+    Anchor::Custom(Vec2::new(to_center.x, -to_center.y) / size)
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        math::{UVec2, Vec2},
+        sprite::Anchor,
+    };
+
+    use super::frame_anchor;
+
+    #[test]
+    fn uncropped_frame_is_centered() {
+        let frame_size = UVec2::new(40, 32);
+
+        let anchor = frame_anchor(frame_size, UVec2::ZERO, frame_size);
+        assert_eq!(anchor.as_vec(), Anchor::Center.as_vec());
+    }
+
+    #[test]
+    fn cropped_frame_keeps_the_full_frame_center_at_the_origin() {
+        let frame_size = UVec2::new(40, 40);
+        let crop_offset = UVec2::new(4, 10);
+        let size = UVec2::new(20, 16);
+
+        let anchor = frame_anchor(size, crop_offset, frame_size);
+
+        // The full frame center, from the cropped frame center with y up
+        let full_center = frame_size.as_vec2() / 2. - crop_offset.as_vec2();
+        let from_center = full_center - size.as_vec2() / 2.;
+        let expected = Vec2::new(from_center.x, -from_center.y);
+        assert_eq!(anchor.as_vec() * size.as_vec2(), expected);
+        assert_eq!(expected, Vec2::new(6., -2.));
+    }
+}
@@ -0,0 +1,134 @@
+use thiserror::Error;
+
+/// Magic bytes, format version and payload checksum
+const HEADER_LEN: usize = 12;
+
+/// Identifies a binary asset format and the version of its layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AssetHeader {
+    pub magic: [u8; 4],
+    pub version: u32,
+}
+
+#[derive(Debug, Error)]
+pub enum AssetHeaderError {
+    #[error("the file is too short to hold an asset header, rebuild the assets")]
+    Truncated,
+    #[error("expected a {expected} file, found magic bytes {found:?}, rebuild the assets")]
+    Magic { expected: String, found: [u8; 4] },
+    #[error("the file has format version {found} but version {expected} is expected, rebuild the assets")]
+    Version { expected: u32, found: u32 },
+    #[error("the file is corrupt, its checksum doesn't match, rebuild the assets")]
+    Checksum,
+}
+
+impl AssetHeader {
+    pub const fn new(magic: &[u8; 4], version: u32) -> Self {
+        Self {
+            magic: *magic,
+            version,
+        }
+    }
+
+    /// Prepend the header to the encoded asset
+    pub fn write(&self, payload: &[u8]) -> Vec<u8> {
+        let mut buffer = Vec::with_capacity(HEADER_LEN + payload.len());
+        buffer.extend_from_slice(&self.magic);
+        buffer.extend_from_slice(&self.version.to_le_bytes());
+        buffer.extend_from_slice(&crc32fast::hash(payload).to_le_bytes());
+        buffer.extend_from_slice(payload);
+        buffer
+    }
+
+    /// Check the header of the buffer and return the encoded asset that follows it
+    pub fn read<'a>(&self, buffer: &'a [u8]) -> Result<&'a [u8], AssetHeaderError> {
+        if buffer.len() < HEADER_LEN {
+            return Err(AssetHeaderError::Truncated);
+        }
+        let (header, payload) = buffer.split_at(HEADER_LEN);
+
+        let magic: [u8; 4] = header[0..4].try_into().unwrap();
+        if magic != self.magic {
+            return Err(AssetHeaderError::Magic {
+                expected: String::from_utf8_lossy(&self.magic).into_owned(),
+                found: magic,
+            });
+        }
+
+        let version = u32::from_le_bytes(header[4..8].try_into().unwrap());
+        if version != self.version {
+            return Err(AssetHeaderError::Version {
+                expected: self.version,
+                found: version,
+            });
+        }
+
+        let checksum = u32::from_le_bytes(header[8..12].try_into().unwrap());
+        if checksum != crc32fast::hash(payload) {
+            return Err(AssetHeaderError::Checksum);
+        }
+
+        Ok(payload)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AssetHeader, AssetHeaderError, HEADER_LEN};
+
+    const HEADER: AssetHeader = AssetHeader::new(b"TEST", 2);
+    const PAYLOAD: &[u8] = b"encoded asset";
+
+    #[test]
+    fn reads_back_the_written_payload() {
+        let buffer = HEADER.write(PAYLOAD);
+
+        assert_eq!(buffer.len(), HEADER_LEN + PAYLOAD.len());
+        assert_eq!(HEADER.read(&buffer).unwrap(), PAYLOAD);
+    }
+
+    #[test]
+    fn rejects_another_format() {
+        let buffer = AssetHeader::new(b"FONT", 2).write(PAYLOAD);
+
+        let error = HEADER.read(&buffer).unwrap_err();
+        assert!(
+            matches!(error, AssetHeaderError::Magic { found, .. } if &found == b"FONT"),
+            "{error:?}"
+        );
+    }
+
+    #[test]
+    fn rejects_another_version() {
+        let buffer = AssetHeader::new(b"TEST", 1).write(PAYLOAD);
+
+        let error = HEADER.read(&buffer).unwrap_err();
+        assert!(
+            matches!(
+                error,
+                AssetHeaderError::Version {
+                    expected: 2,
+                    found: 1
+                }
+            ),
+            "{error:?}"
+        );
+    }
+
+    #[test]
+    fn rejects_a_corrupted_payload() {
+        let mut buffer = HEADER.write(PAYLOAD);
+        *buffer.last_mut().unwrap() ^= 0xFF;
+
+        let error = HEADER.read(&buffer).unwrap_err();
+        assert!(matches!(error, AssetHeaderError::Checksum), "{error:?}");
+    }
+
+    #[test]
+    fn rejects_a_truncated_header() {
+        let buffer = HEADER.write(PAYLOAD);
+
+        let error = HEADER.read(&buffer[..HEADER_LEN - 1]).unwrap_err();
+        assert!(matches!(error, AssetHeaderError::Truncated), "{error:?}");
+    }
+}
@@ -1,2 +1,3 @@
+pub mod asset_header;
 pub mod element;
 pub mod map_status;
@@ -0,0 +1,45 @@
+use bevy::reflect::Reflect;
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter, EnumString, IntoStaticStr};
+
+/// The faces of a portrait folder, named after their raw file
+#[derive(
+    Debug,
+    Reflect,
+    IntoStaticStr,
+    Default,
+    Display,
+    EnumIter,
+    EnumString,
+    Deserialize,
+    Serialize,
+    Eq,
+    PartialEq,
+    Hash,
+    Clone,
+    Copy,
+)]
+pub enum Emotion {
+    #[default]
+    Normal,
+    Happy,
+    Pain,
+    Angry,
+    Worried,
+    Sad,
+    Crying,
+    Shouting,
+    #[strum(to_string = "Teary-Eyed")]
+    TearyEyed,
+    Determined,
+    Joyous,
+    Inspired,
+    Surprised,
+    Dizzy,
+    Special0,
+    Special1,
+    Special2,
+    Special3,
+    Sigh,
+    Stunned,
+}
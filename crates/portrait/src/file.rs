@@ -0,0 +1,51 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{self, Write},
+};
+
+use bincode::error::DecodeError;
+use common::asset_header::{AssetHeader, AssetHeaderError};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::emotion::Emotion;
+
+/// Bump the version when the layout of [`PortraitFile`] changes
+pub const PORTRAIT_HEADER: AssetHeader = AssetHeader::new(b"PRTR", 1);
+
+#[derive(Debug, Error)]
+pub enum PortraitFileError {
+    #[error(transparent)]
+    Header(#[from] AssetHeaderError),
+    #[error("could not decode the portrait, rebuild the assets: {0}")]
+    Decode(#[from] DecodeError),
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct PortraitFile {
+    /// PNG encoded grid of the portraits, `columns` portraits per row
+    pub texture: Vec<u8>,
+    /// Width and height of a portrait
+    pub portrait_size: u32,
+    pub columns: u32,
+    pub rows: u32,
+    /// Index of each emotion in the grid
+    pub emotions: HashMap<Emotion, usize>,
+}
+
+impl PortraitFile {
+    pub fn save(&self, file: &mut File) -> Result<(), io::Error> {
+        let buffer = bincode::serde::encode_to_vec(self, bincode::config::standard())
+            .map_err(io::Error::other)?;
+        file.write_all(&PORTRAIT_HEADER.write(&buffer))?;
+        Ok(())
+    }
+
+    pub fn load(buffer: &[u8]) -> Result<Self, PortraitFileError> {
+        let payload = PORTRAIT_HEADER.read(buffer)?;
+        let (portrait, _): (PortraitFile, usize) =
+            bincode::serde::decode_from_slice(payload, bincode::config::standard())?;
+        Ok(portrait)
+    }
+}
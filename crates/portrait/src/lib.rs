@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+
+use bevy::{
+    app::{App, Plugin},
+    asset::{
+        io::Reader, Asset, AssetApp, AssetLoader, AsyncReadExt, Handle, LoadContext, LoadedAsset,
+    },
+    math::UVec2,
+    reflect::TypePath,
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+        texture::Image,
+    },
+    sprite::{TextureAtlas, TextureAtlasLayout},
+    utils::ConditionalSendFuture,
+};
+use emotion::Emotion;
+use file::{PortraitFile, PortraitFileError};
+use image::ImageError;
+use thiserror::Error;
+
+pub mod emotion;
+pub mod file;
+
+pub struct PortraitPlugin;
+
+impl Plugin for PortraitPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<Portrait>()
+            .init_asset_loader::<PortraitLoader>();
+    }
+}
+
+/// The emotion faces of a pokemon, loaded from .portrait files
+#[derive(TypePath, Asset, Debug)]
+pub struct Portrait {
+    pub texture: Handle<Image>,
+    pub atlas_layout: Handle<TextureAtlasLayout>,
+    pub portrait_size: u32,
+    /// Index of each emotion in the atlas
+    pub emotions: HashMap<Emotion, usize>,
+}
+
+impl Portrait {
+    /// The atlas of the emotion face, the species without this emotion show their
+    /// [`Emotion::Normal`] face
+    pub fn texture_atlas(&self, emotion: Emotion) -> TextureAtlas {
+        let index = self
+            .emotions
+            .get(&emotion)
+            .or_else(|| self.emotions.get(&Emotion::Normal))
+            .copied()
+            .unwrap_or_default();
+        TextureAtlas {
+            layout: self.atlas_layout.clone(),
+            index,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct PortraitLoader;
+
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum PortraitLoaderError {
+    /// An [IO](std::io) Error
+    #[error("Could not load asset: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not parse the asset: {0}")]
+    File(#[from] PortraitFileError),
+    #[error("Could not decompress the portraits texture: {0}")]
+    Texture(#[from] ImageError),
+}
+
+impl AssetLoader for PortraitLoader {
+    type Asset = Portrait;
+    type Settings = ();
+    type Error = PortraitLoaderError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a Self::Settings,
+        load_context: &'a mut LoadContext<'_>,
+    ) -> impl ConditionalSendFuture<Output = Result<Self::Asset, Self::Error>> {
+        async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let portrait_file = PortraitFile::load(&bytes)?;
+
+            let texture_buffer = image::load_from_memory(&portrait_file.texture)?.to_rgba8();
+            let texture = Image::new(
+                Extent3d {
+                    width: texture_buffer.width(),
+                    height: texture_buffer.height(),
+                    depth_or_array_layers: 1,
+                },
+                TextureDimension::D2,
+                texture_buffer.into_raw(),
+                TextureFormat::Rgba8UnormSrgb,
+                // Unload the texture inside de VRAM
+                RenderAssetUsages::RENDER_WORLD,
+            );
+            let texture_handle =
+                load_context.add_loaded_labeled_asset("texture.png", LoadedAsset::from(texture));
+
+            let atlas_layout = TextureAtlasLayout::from_grid(
+                UVec2::splat(portrait_file.portrait_size),
+                portrait_file.columns,
+                portrait_file.rows,
+                None,
+                None,
+            );
+            let atlas_layout_handle = load_context
+                .add_loaded_labeled_asset("atlas_layout", LoadedAsset::from(atlas_layout));
+
+            Ok(Portrait {
+                texture: texture_handle,
+                atlas_layout: atlas_layout_handle,
+                portrait_size: portrait_file.portrait_size,
+                emotions: portrait_file.emotions,
+            })
+        }
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["portrait"]
+    }
+}
@@ -0,0 +1,111 @@
+use std::{
+    fs::File,
+    io::{self, Write},
+};
+
+use bevy::{asset::Asset, reflect::TypePath};
+use serde::{Deserialize, Serialize};
+
+/// A move, with only what the game plays of the raw spell data
+#[derive(TypePath, Asset, Debug, Clone, Serialize, Deserialize)]
+pub struct SpellData {
+    pub name: String,
+    pub category: SpellCategory,
+    /// Base power, 0 for the moves without damage
+    pub power: i32,
+    /// Number of times the move is used in a row
+    pub strikes: u32,
+    pub hitbox: SpellHitbox,
+    /// Played on the caster when the move is used
+    pub cast_visual_effect: Option<String>,
+    /// Played on every piece hit
+    pub hit_visual_effect: Option<String>,
+    pub explosion: Option<SpellExplosion>,
+    /// Applied to every tile hit
+    pub tile_effects: Vec<SpellTileEffect>,
+}
+
+impl SpellData {
+    pub fn load(buffer: &[u8]) -> Result<Self, ron::Error> {
+        let spell_data = ron::de::from_bytes(buffer)?;
+        Ok(spell_data)
+    }
+
+    pub fn save(&self, file: &mut File) -> Result<(), io::Error> {
+        let buffer = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default()).unwrap();
+        file.write_all(buffer.as_bytes())?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SpellCategory {
+    Physical,
+    Special,
+    Status,
+}
+
+/// The tiles the move reaches from the caster
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SpellHitbox {
+    /// The tile in front of the caster
+    Melee,
+    /// Straight lines from the caster, up to `range` tiles away
+    Projectile {
+        range: i32,
+        rays: SpellRays,
+        stop_at_wall: bool,
+        stop_at_hit: bool,
+        boomerang: bool,
+        visual_effect: Option<String>,
+    },
+    /// The tile of the caster, e.g. to explode around it
+    Caster,
+}
+
+/// Directions a projectile is fired in, same order as the `Rays` of the raw spell data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SpellRays {
+    One,
+    Three,
+    Five,
+    Four,
+    Eight,
+}
+
+/// What a move does to the tiles it hits, the `OnHitTiles` of the raw spell data
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SpellTileEffect {
+    /// Turn the tiles of these terrains into ground, e.g. Rock Smash shattering walls
+    ClearTerrain(Vec<SpellTerrain>),
+    /// Set the trap of this raw ID, e.g. `trap_spikes`
+    SetTrap(String),
+    RemoveTrap,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SpellTerrain {
+    Wall,
+    Water,
+    Lava,
+    Abyss,
+    Foliage,
+}
+
+/// Splash damage around the tile where the move lands
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpellExplosion {
+    /// Distance to the impact tile, diagonals included
+    pub range: i32,
+    pub targets: SpellTargets,
+    /// Played on every tile of the explosion
+    pub visual_effect: Option<String>,
+}
+
+/// The pieces a move can hit, relative to the caster
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpellTargets {
+    pub caster: bool,
+    pub allies: bool,
+    pub foes: bool,
+}
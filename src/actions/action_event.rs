@@ -0,0 +1,31 @@
+use super::{
+    damage_action::DamageAction, death_action::DeathAction, evolve_action::EvolveAction,
+    leave_action::LeaveAction, melee_hit_action::MeleeHitAction,
+    modify_terrain_action::ModifyTerrainAction, recruit_action::RecruitAction,
+    skip_action::SkipAction, spell_action::SpellAction,
+    spell_explosion_action::SpellExplosionAction, spell_hit_action::SpellHitAction,
+    spell_projectile_action::SpellProjectileAction, spell_strike_action::SpellStrikeAction,
+    trigger_trap_action::TriggerTrapAction, walk_action::WalkAction,
+};
+
+/// What an executed action did, the logs and animations match on it instead of the action type
+#[derive(Debug, Clone)]
+pub enum ActionEvent {
+    Walk(WalkAction),
+    MeleeHit(MeleeHitAction),
+    Damage(DamageAction),
+    Death(DeathAction),
+    /* #region spells */
+    Spell(SpellAction),
+    SpellStrike(SpellStrikeAction),
+    SpellProjectile(SpellProjectileAction),
+    SpellHit(SpellHitAction),
+    SpellExplosion(SpellExplosionAction),
+    /* #endregion */
+    ModifyTerrain(ModifyTerrainAction),
+    TriggerTrap(TriggerTrapAction),
+    Recruit(RecruitAction),
+    Leave(LeaveAction),
+    Evolve(EvolveAction),
+    Skip(SkipAction),
+}
@@ -4,10 +4,11 @@ use bevy::{prelude::*, utils::info};
 
 use crate::{
     actions::{ActionExecutedEvent, RunningAction},
+    map::Position,
     stats::Health,
 };
 
-use super::{Action, ProcessingActionEvent};
+use super::{Action, ActionPhase, ActionRejectedEvent, ProcessingActionEvent};
 
 #[derive(Debug, Clone)]
 pub struct QueuedAction {
@@ -18,6 +19,20 @@ pub struct QueuedAction {
 #[derive(Resource, Default, Clone)]
 pub struct ActionQueue(pub VecDeque<QueuedAction>);
 
+/// The actions resolved together, the next batch only starts once all of them are animated
+#[derive(Resource, Debug, Default)]
+pub struct ActionBatch {
+    /// Incremented for each new batch
+    pub id: u32,
+    pub phase: ActionPhase,
+    /// The entities running an action of the batch
+    pub entities: Vec<Entity>,
+}
+
+/// The longest reach of a move plus a walk, the walks closer than that to a pending action are
+/// considered interacting with it and keep their turn order
+const INTERACTION_RANGE: i32 = 4;
+
 pub fn process_action_queue(world: &mut World) {
     let mut running_action_query = world.query_filtered::<Entity, With<RunningAction>>();
 
@@ -26,18 +41,13 @@ pub fn process_action_queue(world: &mut World) {
         return;
     }
 
-    'queue_action_loop: loop {
-        // Get the first action of the queue
-        let queued_action = {
-            let mut action_queue = world.resource_mut::<ActionQueue>();
-            if action_queue.0.is_empty() {
-                // If the ActionQueue is empty, return
-                break;
-            }
-            action_queue.0.pop_front()
-        };
+    let mut batch_phase = None;
+    // The actions left for a next batch, in turn order
+    let mut pending: Vec<QueuedAction> = vec![];
 
-        let Some(queued_action) = queued_action else {
+    loop {
+        // Get the first action of the queue
+        let Some(queued_action) = world.resource_mut::<ActionQueue>().0.pop_front() else {
             // If there is no more action in the queue
             break;
         };
@@ -51,46 +61,233 @@ pub fn process_action_queue(world: &mut World) {
             }
         }
 
-        for (action_index, action) in queued_action.performable_actions.iter().enumerate() {
-            match action.execute(world) {
-                Ok(result_actions) => {
-                    // Action well executed (insert the `RunningAction`)
-                    info!("action executed {:?}", action);
-                    world.send_event(ActionExecutedEvent {
-                        action: action.clone(),
+        let phase = queued_action
+            .performable_actions
+            .iter()
+            .find(|action| action.can_execute(world).is_ok())
+            .map_or(ActionPhase::Sequential, |action| action.phase());
+
+        match (batch_phase, phase) {
+            (None, _) => {}
+            (Some(ActionPhase::Movement), ActionPhase::Movement)
+                if world.get::<RunningAction>(queued_action.entity).is_none()
+                    && !interacts_with_pending(world, queued_action.entity, &pending) => {}
+            (Some(ActionPhase::Movement), _) => {
+                // Wait for the movements to be over, the next walks can still overtake it
+                pending.push(queued_action);
+                continue;
+            }
+            (Some(ActionPhase::Sequential), _) => {
+                // Avoid processing multiple action at the same time
+                pending.push(queued_action);
+                break;
+            }
+        }
+
+        let Some(executed) = execute_queued_action(world, &queued_action) else {
+            continue;
+        };
+
+        let mut batch = world.resource_mut::<ActionBatch>();
+        if batch_phase.is_none() {
+            batch.id += 1;
+            batch.phase = executed.phase();
+            batch.entities.clear();
+            batch_phase = Some(batch.phase);
+        }
+        if !batch.entities.contains(&queued_action.entity) {
+            batch.entities.push(queued_action.entity);
+        }
+
+        apply_deferred(world);
+    }
+
+    let mut action_queue = world.resource_mut::<ActionQueue>();
+    for queued_action in pending.into_iter().rev() {
+        action_queue.0.push_front(queued_action);
+    }
+}
+
+/// Execute the first performable action of the queued ones, and queue what it leads to
+fn execute_queued_action(
+    world: &mut World,
+    queued_action: &QueuedAction,
+) -> Option<Box<dyn Action>> {
+    let mut rejected = vec![];
+    let mut executed = None;
+    for action in queued_action.performable_actions.iter() {
+        match action.execute(world) {
+            Ok(outcome) => {
+                // Action well executed (insert the `RunningAction`)
+                info!("action executed {:?}", action);
+                let event = action.event();
+                world.send_event(ActionExecutedEvent {
+                    action: action.clone(),
+                    event: event.clone(),
+                    entity: queued_action.entity,
+                });
+                world
+                    .entity_mut(queued_action.entity)
+                    .insert(RunningAction(event));
+
+                let mut action_queue = world.get_resource_mut::<ActionQueue>().unwrap();
+                for next_action in outcome.sequence.into_iter().rev() {
+                    action_queue.0.push_front(QueuedAction {
                         entity: queued_action.entity,
+                        performable_actions: vec![next_action],
                     });
-                    world
-                        .entity_mut(queued_action.entity)
-                        .insert(RunningAction(action.clone()));
-
-                    if !result_actions.is_empty() {
-                        let mut action_queue = world.get_resource_mut::<ActionQueue>().unwrap();
-                        action_queue.0.push_front(QueuedAction {
-                            entity: queued_action.entity,
-                            performable_actions: result_actions,
-                        });
-                    }
-
-                    if action.is_parallel_execution() {
-                        // go to the next queue action
-                        break;
-                    }
-
-                    // Avoid processing multiple action at the same time
-                    break 'queue_action_loop;
                 }
-                Err(_) => {
-                    warn!("Action not valid");
-                    if action_index == queued_action.performable_actions.len() - 1 {
-                        // Last performable action is also invalid
-                        warn!("No more performable action");
-                        break;
-                    }
+                if !outcome.next_actions.is_empty() {
+                    action_queue.0.push_front(QueuedAction {
+                        entity: queued_action.entity,
+                        performable_actions: outcome.next_actions,
+                    });
                 }
-            };
+
+                executed = Some(action.clone());
+                break;
+            }
+            Err(error) => {
+                warn!("Action not valid: {:?} ({})", action, error);
+                rejected.push((action.clone(), error));
+            }
+        };
+    }
+
+    if executed.is_none() {
+        warn!("No more performable action");
+    }
+    if !rejected.is_empty() {
+        world.send_event(ActionRejectedEvent {
+            entity: queued_action.entity,
+            rejected,
+            executed: executed.clone(),
+        });
+    }
+
+    executed
+}
+
+/// Whether the entity acts later in the turn or is in reach of an action waiting for its turn
+fn interacts_with_pending(world: &World, entity: Entity, pending: &[QueuedAction]) -> bool {
+    let Some(position) = world.get::<Position>(entity) else {
+        return true;
+    };
+
+    pending.iter().any(|queued_action| {
+        queued_action.entity == entity
+            || world
+                .get::<Position>(queued_action.entity)
+                .is_some_and(|p| (p.0 - position.0).abs().max_element() <= INTERACTION_RANGE)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use crate::{
+        actions::{
+            melee_hit_action::MeleeHitAction, walk_action::WalkAction, Action, ActionPhase,
+            ActionQueue, RunningAction,
+        },
+        faction::Faction,
+        test_harness::TestWorld,
+    };
+
+    use super::{process_action_queue, ActionBatch};
+
+    fn walk(entity: Entity, from: IVec2, to: IVec2) -> Vec<Box<dyn Action>> {
+        vec![Box::new(WalkAction { entity, from, to })]
+    }
+
+    fn running(test_world: &mut TestWorld) -> Vec<Entity> {
+        test_world
+            .world
+            .query_filtered::<Entity, With<RunningAction>>()
+            .iter(&test_world.world)
+            .collect()
+    }
+
+    fn end_batch(test_world: &mut TestWorld) {
+        for entity in running(test_world) {
+            test_world
+                .world
+                .entity_mut(entity)
+                .remove::<RunningAction>();
         }
+    }
 
-        apply_deferred(world);
+    #[test]
+    fn far_walks_overtake_an_attack() {
+        let mut test_world = TestWorld::new(TestWorld::map(16, 5, &[]));
+        let first = test_world.spawn_piece(IVec2::new(14, 1), Faction::Foe, 10);
+        let attacker = test_world.spawn_piece(IVec2::new(1, 1), Faction::Foe, 10);
+        let target = test_world.spawn_piece(IVec2::new(2, 1), Faction::Player, 10);
+        let far = test_world.spawn_piece(IVec2::new(14, 3), Faction::Foe, 10);
+        let near = test_world.spawn_piece(IVec2::new(3, 3), Faction::Foe, 10);
+
+        test_world.enqueue(first, walk(first, IVec2::new(14, 1), IVec2::new(13, 1)));
+        test_world.enqueue(
+            attacker,
+            vec![Box::new(MeleeHitAction {
+                attacker,
+                target: IVec2::new(2, 1),
+                damage: 1,
+            })],
+        );
+        test_world.enqueue(far, walk(far, IVec2::new(14, 3), IVec2::new(13, 3)));
+        test_world.enqueue(near, walk(near, IVec2::new(3, 3), IVec2::new(3, 2)));
+
+        process_action_queue(&mut test_world.world);
+
+        let batch = test_world.world.resource::<ActionBatch>();
+        assert_eq!(batch.phase, ActionPhase::Movement);
+        assert_eq!(batch.entities, vec![first, far]);
+        let queue = test_world.world.resource::<ActionQueue>();
+        assert_eq!(
+            queue.0.iter().map(|q| q.entity).collect::<Vec<_>>(),
+            vec![attacker, near]
+        );
+
+        // The attack is resolved alone before the walk in its reach
+        end_batch(&mut test_world);
+        process_action_queue(&mut test_world.world);
+        assert_eq!(running(&mut test_world), vec![attacker]);
+        assert_eq!(
+            test_world.world.resource::<ActionBatch>().phase,
+            ActionPhase::Sequential
+        );
+
+        end_batch(&mut test_world);
+        test_world.run();
+        test_world.assert_health(target, 9);
+        test_world.assert_executed::<WalkAction>(3);
+    }
+
+    #[test]
+    fn attacks_are_resolved_one_at_a_time() {
+        let mut test_world = TestWorld::new(TestWorld::map(5, 5, &[]));
+        let target = test_world.spawn_piece(IVec2::new(2, 2), Faction::Player, 10);
+        let attackers = [IVec2::new(1, 2), IVec2::new(3, 2)]
+            .map(|position| test_world.spawn_piece(position, Faction::Foe, 10));
+
+        for attacker in attackers {
+            test_world.enqueue(
+                attacker,
+                vec![Box::new(MeleeHitAction {
+                    attacker,
+                    target: IVec2::new(2, 2),
+                    damage: 1,
+                })],
+            );
+        }
+
+        process_action_queue(&mut test_world.world);
+        assert_eq!(running(&mut test_world), vec![attackers[0]]);
+
+        end_batch(&mut test_world);
+        test_world.run();
+        test_world.assert_health(target, 8);
     }
 }
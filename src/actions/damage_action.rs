@@ -1,13 +1,19 @@
 use bevy::prelude::*;
 
 use crate::{
+    evolution::KillCount,
+    faction::Faction,
     map::Position,
     move_type::MoveCategory,
     pieces::PieceDeathEvent,
-    stats::{Health, Stats},
+    recruitment::roll_recruitment,
+    stats::{defeat_experience, gain_experience, Health, Stats},
 };
 
-use super::{death_action::DeathAction, orient_entity, Action};
+use super::{
+    death_action::DeathAction, orient_entity, recruit_action::RecruitAction, Action, ActionError,
+    ActionEvent, ActionOutcome, ActionPhase, ActionResult,
+};
 
 #[derive(Debug, Clone)]
 pub struct DamageAction {
@@ -18,18 +24,17 @@ pub struct DamageAction {
 }
 
 impl Action for DamageAction {
-    fn execute(&self, world: &mut World) -> Result<Vec<Box<dyn Action>>, ()> {
-        if !self.can_execute(world) {
-            return Err(());
-        };
+    fn execute(&self, world: &mut World) -> ActionResult {
+        self.can_execute(world)?;
 
-        let Some(mut health) = world.get_mut::<Health>(self.target) else {
-            return Err(());
-        };
+        let mut health = world
+            .get_mut::<Health>(self.target)
+            .ok_or(ActionError::TargetMissing)?;
 
         let health_before = health.value;
         health.value = health.value.saturating_sub(self.value);
         let health_after = health.value;
+        let is_dead = health.is_dead();
         if let Some((attack_stat, _)) = self.move_type.get_damage_stats() {
             info!(
                 "Applied {} damage (using {}). Health: {}→{}",
@@ -40,8 +45,29 @@ impl Action for DamageAction {
             );
         }
 
+        if is_dead && self.attacker != self.target {
+            if let Some(mut attacker) = world.get_entity_mut(self.attacker) {
+                let kill_count = attacker.get::<KillCount>().map(|k| k.0).unwrap_or(0);
+                attacker.insert(KillCount(kill_count + 1));
+            }
+
+            // Only the team grows from its fights
+            if world
+                .get::<Faction>(self.attacker)
+                .is_some_and(|faction| faction.is_team())
+            {
+                let experience = defeat_experience(world, self.target);
+                gain_experience(world, self.attacker, experience);
+            }
+        }
+
         let mut next_actions = vec![];
-        if health.is_dead() {
+        if is_dead && roll_recruitment(world, self.attacker, self.target) {
+            next_actions.push(Box::new(RecruitAction {
+                recruiter: self.attacker,
+                target: self.target,
+            }) as Box<dyn Action>);
+        } else if is_dead {
             world.send_event(PieceDeathEvent {
                 entity: self.target,
             });
@@ -51,27 +77,127 @@ impl Action for DamageAction {
             }) as Box<dyn Action>);
         }
 
-        let attacker_position = world.get::<Position>(self.attacker).ok_or(())?;
+        let attacker_position = world
+            .get::<Position>(self.attacker)
+            .ok_or(ActionError::TargetMissing)?;
 
         orient_entity(world, self.target, attacker_position.0);
 
-        Ok(next_actions)
+        Ok(ActionOutcome::then(next_actions))
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
 
-    fn is_parallel_execution(&self) -> bool {
-        false
+    fn event(&self) -> ActionEvent {
+        ActionEvent::Damage(self.clone())
     }
 
-    fn can_execute(&self, world: &mut World) -> bool {
+    fn phase(&self) -> ActionPhase {
+        ActionPhase::Sequential
+    }
+
+    fn can_execute(&self, world: &mut World) -> Result<(), ActionError> {
         // Status moves don't deal direct damage
-        if let Some(_) = self.move_type.get_damage_stats() {
-            world.get::<Health>(self.target).is_some()
-        } else {
-            false
+        if self.move_type.get_damage_stats().is_none() {
+            return Err(ActionError::NoEffect);
+        }
+        let health = world
+            .get::<Health>(self.target)
+            .ok_or(ActionError::TargetMissing)?;
+        // Already defeated by a previous hit
+        if health.is_dead() {
+            return Err(ActionError::TargetMissing);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::event::Events, prelude::*};
+
+    use crate::{
+        actions::{death_action::DeathAction, ActionError},
+        evolution::KillCount,
+        faction::Faction,
+        move_type::MoveCategory,
+        pieces::PieceDeathEvent,
+        test_harness::TestWorld,
+    };
+
+    use super::DamageAction;
+
+    fn damage(
+        attacker: Entity,
+        target: Entity,
+        value: i32,
+        move_type: MoveCategory,
+    ) -> DamageAction {
+        DamageAction {
+            attacker,
+            target,
+            value,
+            move_type,
         }
     }
+
+    #[test]
+    fn reduces_the_target_health() {
+        let mut test_world = TestWorld::new(TestWorld::map(5, 5, &[]));
+        let attacker = test_world.spawn_piece(IVec2::new(1, 1), Faction::None, 10);
+        let target = test_world.spawn_piece(IVec2::new(1, 2), Faction::None, 10);
+
+        test_world.enqueue(
+            attacker,
+            vec![Box::new(damage(
+                attacker,
+                target,
+                4,
+                MoveCategory::Physical,
+            ))],
+        );
+        test_world.run();
+
+        test_world.assert_health(target, 6);
+        test_world.assert_executed::<DeathAction>(0);
+    }
+
+    #[test]
+    fn status_moves_deal_no_damage() {
+        let mut test_world = TestWorld::new(TestWorld::map(5, 5, &[]));
+        let attacker = test_world.spawn_piece(IVec2::new(1, 1), Faction::None, 10);
+        let target = test_world.spawn_piece(IVec2::new(1, 2), Faction::None, 10);
+
+        test_world.enqueue(
+            attacker,
+            vec![Box::new(damage(attacker, target, 4, MoveCategory::Status))],
+        );
+        test_world.run();
+
+        test_world.assert_health(target, 10);
+        test_world.assert_executed::<DamageAction>(0);
+        test_world.assert_rejected::<DamageAction>(ActionError::NoEffect);
+    }
+
+    #[test]
+    fn lethal_damage_defeats_the_target() {
+        let mut test_world = TestWorld::new(TestWorld::map(5, 5, &[]));
+        let attacker = test_world.spawn_piece(IVec2::new(1, 1), Faction::None, 10);
+        let target = test_world.spawn_piece(IVec2::new(1, 2), Faction::None, 3);
+
+        test_world.enqueue(
+            attacker,
+            vec![Box::new(damage(attacker, target, 5, MoveCategory::Special))],
+        );
+        test_world.run();
+
+        test_world.assert_executed::<DeathAction>(1);
+        assert_eq!(
+            test_world.world.resource::<Events<PieceDeathEvent>>().len(),
+            1
+        );
+        assert_eq!(test_world.world.get::<KillCount>(attacker).unwrap().0, 1);
+    }
 }
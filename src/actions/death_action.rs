@@ -2,7 +2,7 @@ use bevy::prelude::*;
 
 use crate::stats::{Health, Stats};
 
-use super::Action;
+use super::{Action, ActionError, ActionEvent, ActionOutcome, ActionPhase, ActionResult};
 
 #[derive(Debug, Clone)]
 pub struct DeathAction {
@@ -11,26 +11,31 @@ pub struct DeathAction {
 }
 
 impl Action for DeathAction {
-    fn execute(&self, world: &mut World) -> Result<Vec<Box<dyn Action>>, ()> {
-        if !self.can_execute(world) {
-            return Err(());
-        };
+    fn execute(&self, world: &mut World) -> ActionResult {
+        self.can_execute(world)?;
 
-        Ok(Vec::new())
+        Ok(ActionOutcome::done())
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
 
-    fn is_parallel_execution(&self) -> bool {
-        false
+    fn event(&self) -> ActionEvent {
+        ActionEvent::Death(self.clone())
     }
 
-    fn can_execute(&self, world: &mut World) -> bool {
-        return world
+    fn phase(&self) -> ActionPhase {
+        ActionPhase::Sequential
+    }
+
+    fn can_execute(&self, world: &mut World) -> Result<(), ActionError> {
+        let health = world
             .get::<Health>(self.target)
-            .map(|health: &Health| health.is_dead())
-            .unwrap_or(false);
+            .ok_or(ActionError::TargetMissing)?;
+        if !health.is_dead() {
+            return Err(ActionError::NoEffect);
+        }
+        Ok(())
     }
 }
@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+
+use crate::pokemons::Pokemon;
+
+use super::{Action, ActionError, ActionEvent, ActionOutcome, ActionPhase, ActionResult};
+
+#[derive(Debug, Clone)]
+pub struct EvolveAction {
+    pub entity: Entity,
+    pub id: u32,
+    pub form_index: usize,
+}
+
+impl Action for EvolveAction {
+    fn execute(&self, world: &mut World) -> ActionResult {
+        self.can_execute(world)?;
+
+        // Changing the pokemon updates its data handle and its stats
+        let mut pokemon = world
+            .get_mut::<Pokemon>(self.entity)
+            .ok_or(ActionError::TargetMissing)?;
+        pokemon.id = self.id;
+        pokemon.form_index = self.form_index;
+
+        Ok(ActionOutcome::done())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn event(&self) -> ActionEvent {
+        ActionEvent::Evolve(self.clone())
+    }
+
+    fn phase(&self) -> ActionPhase {
+        ActionPhase::Sequential
+    }
+
+    fn can_execute(&self, world: &mut World) -> Result<(), ActionError> {
+        let pokemon = world
+            .get::<Pokemon>(self.entity)
+            .ok_or(ActionError::TargetMissing)?;
+        if pokemon.id == self.id && pokemon.form_index == self.form_index {
+            return Err(ActionError::NoEffect);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,99 @@
+use bevy::prelude::*;
+
+use crate::{pieces::Occupier, turn::TurnOrder};
+
+use super::{Action, ActionError, ActionEvent, ActionOutcome, ActionPhase, ActionResult};
+
+/// The piece walks away from the floor, it is removed once its animation is over
+#[derive(Component, Debug)]
+pub struct LeftFloor;
+
+#[derive(Debug, Clone)]
+pub struct LeaveAction {
+    pub entity: Entity,
+}
+
+impl Action for LeaveAction {
+    fn execute(&self, world: &mut World) -> ActionResult {
+        self.can_execute(world)?;
+
+        world
+            .resource_mut::<TurnOrder>()
+            .0
+            .retain(|actor| *actor != self.entity);
+        world
+            .entity_mut(self.entity)
+            .remove::<Occupier>()
+            .insert(LeftFloor);
+
+        Ok(ActionOutcome::done())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn event(&self) -> ActionEvent {
+        ActionEvent::Leave(self.clone())
+    }
+
+    fn phase(&self) -> ActionPhase {
+        ActionPhase::Sequential
+    }
+
+    fn can_execute(&self, world: &mut World) -> Result<(), ActionError> {
+        let Some(entity) = world.get_entity(self.entity) else {
+            return Err(ActionError::TargetMissing);
+        };
+        if entity.contains::<LeftFloor>() {
+            return Err(ActionError::NoEffect);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use crate::{
+        actions::ActionError, evolution::KillCount, faction::Faction, pieces::Occupier,
+        test_harness::TestWorld, turn::TurnOrder,
+    };
+
+    use super::{LeaveAction, LeftFloor};
+
+    #[test]
+    fn leaving_piece_is_out_of_the_turn_order_without_dying() {
+        let mut test_world = TestWorld::new(TestWorld::map(5, 5, &[]));
+        let player = test_world.spawn_piece(IVec2::new(1, 2), Faction::Player, 10);
+        let foe = test_world.spawn_piece(IVec2::new(2, 2), Faction::Foe, 10);
+
+        test_world.enqueue(foe, vec![Box::new(LeaveAction { entity: foe })]);
+        test_world.run();
+
+        test_world.assert_executed::<LeaveAction>(1);
+        test_world.assert_health(foe, 10);
+        assert!(test_world.world.get::<LeftFloor>(foe).is_some());
+        assert!(test_world.world.get::<Occupier>(foe).is_none());
+        assert!(test_world.world.get::<KillCount>(player).is_none());
+        assert_eq!(
+            test_world.world.resource::<TurnOrder>().0,
+            vec![player],
+            "The piece that left still takes turns"
+        );
+    }
+
+    #[test]
+    fn piece_cannot_leave_twice() {
+        let mut test_world = TestWorld::new(TestWorld::map(5, 5, &[]));
+        let foe = test_world.spawn_piece(IVec2::new(2, 2), Faction::Foe, 10);
+
+        test_world.enqueue(foe, vec![Box::new(LeaveAction { entity: foe })]);
+        test_world.enqueue(foe, vec![Box::new(LeaveAction { entity: foe })]);
+        test_world.run();
+
+        test_world.assert_executed::<LeaveAction>(1);
+        test_world.assert_rejected::<LeaveAction>(ActionError::NoEffect);
+    }
+}
@@ -7,7 +7,13 @@ use crate::{
     stats::{Health, Stats},
 };
 
-use super::{damage_action::DamageAction, orient_entity, Action};
+use super::{
+    damage_action::DamageAction, orient_entity, Action, ActionError, ActionEvent, ActionOutcome,
+    ActionPhase, ActionResult,
+};
+
+/// The damage of the basic attack, when a piece bumps into another one
+pub const BASIC_ATTACK_DAMAGE: i32 = 1;
 
 #[derive(Debug, Clone)]
 pub struct MeleeHitAction {
@@ -17,10 +23,8 @@ pub struct MeleeHitAction {
 }
 
 impl Action for MeleeHitAction {
-    fn execute(&self, world: &mut World) -> Result<Vec<Box<dyn Action>>, ()> {
-        if !self.can_execute(world) {
-            return Err(());
-        };
+    fn execute(&self, world: &mut World) -> ActionResult {
+        self.can_execute(world)?;
 
         let target_entities = world
             .query_filtered::<(Entity, &Position), With<Health>>()
@@ -29,7 +33,7 @@ impl Action for MeleeHitAction {
             .collect::<Vec<_>>();
 
         if target_entities.is_empty() {
-            return Err(());
+            return Err(ActionError::TargetMissing);
         };
 
         let result = target_entities
@@ -46,23 +50,27 @@ impl Action for MeleeHitAction {
 
         orient_entity(world, self.attacker, self.target);
 
-        Ok(result)
+        Ok(ActionOutcome::then(result))
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
 
-    fn is_parallel_execution(&self) -> bool {
-        false
+    fn event(&self) -> ActionEvent {
+        ActionEvent::MeleeHit(self.clone())
     }
 
-    fn can_execute(&self, world: &mut World) -> bool {
-        let Some(attacker_position) = world.get::<Position>(self.attacker) else {
-            return false;
-        };
+    fn phase(&self) -> ActionPhase {
+        ActionPhase::Sequential
+    }
+
+    fn can_execute(&self, world: &mut World) -> Result<(), ActionError> {
+        let attacker_position = world
+            .get::<Position>(self.attacker)
+            .ok_or(ActionError::TargetMissing)?;
         if attacker_position.0.manhattan(self.target) > 1 {
-            return false;
+            return Err(ActionError::OutOfRange);
         };
 
         let target_entities = world
@@ -72,9 +80,84 @@ impl Action for MeleeHitAction {
             .collect::<Vec<_>>();
 
         if target_entities.is_empty() {
-            return false;
+            return Err(ActionError::TargetMissing);
         };
 
-        true
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use crate::{
+        actions::{damage_action::DamageAction, death_action::DeathAction, ActionError},
+        faction::Faction,
+        test_harness::TestWorld,
+    };
+
+    use super::MeleeHitAction;
+
+    #[test]
+    fn hits_an_adjacent_target() {
+        let mut test_world = TestWorld::new(TestWorld::map(5, 5, &[]));
+        let attacker = test_world.spawn_piece(IVec2::new(1, 1), Faction::None, 10);
+        let target = test_world.spawn_piece(IVec2::new(1, 2), Faction::None, 10);
+
+        test_world.enqueue(
+            attacker,
+            vec![Box::new(MeleeHitAction {
+                attacker,
+                target: IVec2::new(1, 2),
+                damage: 3,
+            })],
+        );
+        test_world.run();
+
+        test_world.assert_health(target, 7);
+        test_world.assert_health(attacker, 10);
+        test_world.assert_executed::<DamageAction>(1);
+    }
+
+    #[test]
+    fn cannot_hit_a_distant_target() {
+        let mut test_world = TestWorld::new(TestWorld::map(5, 5, &[]));
+        let attacker = test_world.spawn_piece(IVec2::new(1, 1), Faction::None, 10);
+        let target = test_world.spawn_piece(IVec2::new(1, 3), Faction::None, 10);
+
+        test_world.enqueue(
+            attacker,
+            vec![Box::new(MeleeHitAction {
+                attacker,
+                target: IVec2::new(1, 3),
+                damage: 3,
+            })],
+        );
+        test_world.run();
+
+        test_world.assert_health(target, 10);
+        test_world.assert_executed::<MeleeHitAction>(0);
+        test_world.assert_rejected::<MeleeHitAction>(ActionError::OutOfRange);
+    }
+
+    #[test]
+    fn defeats_a_target_without_health_left() {
+        let mut test_world = TestWorld::new(TestWorld::map(5, 5, &[]));
+        let attacker = test_world.spawn_piece(IVec2::new(1, 1), Faction::None, 10);
+        let target = test_world.spawn_piece(IVec2::new(2, 1), Faction::None, 2);
+
+        test_world.enqueue(
+            attacker,
+            vec![Box::new(MeleeHitAction {
+                attacker,
+                target: IVec2::new(2, 1),
+                damage: 3,
+            })],
+        );
+        test_world.run();
+
+        test_world.assert_health(target, -1);
+        test_world.assert_executed::<DeathAction>(1);
     }
 }
@@ -3,17 +3,26 @@ use bevy::prelude::*;
 use char_animation::orientation::Orientation;
 use dyn_clone::DynClone;
 use std::{any::Any, fmt::Debug};
+use thiserror::Error;
 
+pub use self::action_event::*;
 pub use self::action_queue::*;
+mod action_event;
 mod action_queue;
 pub mod damage_action;
 pub mod death_action;
-pub mod destroy_wall_action;
+pub mod evolve_action;
+pub mod leave_action;
 pub mod melee_hit_action;
+pub mod modify_terrain_action;
+pub mod recruit_action;
 pub mod skip_action;
 pub mod spell_action;
+pub mod spell_explosion_action;
 pub mod spell_hit_action;
 pub mod spell_projectile_action;
+pub mod spell_strike_action;
+pub mod trigger_trap_action;
 pub mod walk_action;
 
 pub struct ActionsPlugin;
@@ -21,9 +30,11 @@ pub struct ActionsPlugin;
 impl Plugin for ActionsPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<ActionExecutedEvent>()
+            .add_event::<ActionRejectedEvent>()
             .add_event::<ActionQueueProcessedEvent>()
             .add_event::<ProcessingActionEvent>()
             .init_resource::<ActionQueue>()
+            .init_resource::<ActionBatch>()
             .add_systems(
                 Update,
                 (process_action_queue).in_set(GamePlayingSet::Actions),
@@ -33,15 +44,91 @@ impl Plugin for ActionsPlugin {
 
 dyn_clone::clone_trait_object!(Action);
 pub trait Action: Send + Sync + DynClone + Debug {
-    fn execute(&self, world: &mut World) -> Result<Vec<Box<dyn Action>>, ()>;
-    fn can_execute(&self, world: &mut World) -> bool;
+    fn execute(&self, world: &mut World) -> ActionResult;
+    fn can_execute(&self, world: &mut World) -> Result<(), ActionError>;
     fn as_any(&self) -> &dyn Any;
-    fn is_parallel_execution(&self) -> bool;
+    fn event(&self) -> ActionEvent;
+    fn phase(&self) -> ActionPhase;
+}
+
+/// How an action is resolved relative to the other actions of the turn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ActionPhase {
+    /// Batched with the other movements of the turn that don't interact with a pending action,
+    /// and animated together
+    Movement,
+    /// Resolved alone, in turn order
+    #[default]
+    Sequential,
+}
+
+pub type ActionResult = Result<ActionOutcome, ActionError>;
+
+/// What a successfully executed action leads to
+#[derive(Debug, Default)]
+pub struct ActionOutcome {
+    /// Actions executed right after this one, on behalf of the same entity, only the first
+    /// performable one is executed
+    pub next_actions: Vec<Box<dyn Action>>,
+    /// Actions executed one after the other once `next_actions` is resolved, each on its own
+    pub sequence: Vec<Box<dyn Action>>,
+}
+
+impl ActionOutcome {
+    pub fn done() -> Self {
+        Self::default()
+    }
+
+    pub fn then(next_actions: Vec<Box<dyn Action>>) -> Self {
+        Self {
+            next_actions,
+            ..default()
+        }
+    }
+
+    pub fn then_each(sequence: Vec<Box<dyn Action>>) -> Self {
+        Self {
+            sequence,
+            ..default()
+        }
+    }
+}
+
+/// Why an action could not be executed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum ActionError {
+    #[error("the way is blocked")]
+    BlockedByTerrain,
+    #[error("someone is in the way")]
+    Occupied,
+    #[error("there is no target")]
+    TargetMissing,
+    #[error("the target is out of range")]
+    OutOfRange,
+    #[error("it can't move")]
+    Immobilized,
+    // TODO: Moves PP and belly are not part of the game state yet
+    #[allow(dead_code)]
+    #[error("there is no PP left")]
+    NoPP,
+    #[allow(dead_code)]
+    #[error("it's too hungry")]
+    NotEnoughBelly,
+    #[error("it has no effect")]
+    NoEffect,
+}
+
+/// The candidate actions of a queued action that were rejected, and the one executed if any
+#[derive(Event, Debug)]
+pub struct ActionRejectedEvent {
+    pub entity: Entity,
+    pub rejected: Vec<(Box<dyn Action>, ActionError)>,
+    pub executed: Option<Box<dyn Action>>,
 }
 
 /// Current executed action attached to an entity
 #[derive(Component)]
-pub struct RunningAction(pub Box<dyn Action>);
+pub struct RunningAction(pub ActionEvent);
 
 #[derive(Component, Default, Clone)]
 pub struct NextActions(pub Vec<Box<dyn Action>>);
@@ -52,6 +139,7 @@ pub struct ProcessingActionEvent;
 #[derive(Event, Debug)]
 pub struct ActionExecutedEvent {
     pub action: Box<dyn Action>,
+    pub event: ActionEvent,
     pub entity: Entity,
 }
 
@@ -63,6 +151,9 @@ pub fn orient_entity(world: &mut World, entity: Entity, target: IVec2) {
         return;
     };
     let direction = target - grid_position.0;
+    if direction == IVec2::ZERO {
+        return;
+    }
 
     let Some(mut facing_orientation) = world.get_mut::<FacingOrientation>(entity) else {
         return;
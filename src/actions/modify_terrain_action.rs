@@ -0,0 +1,228 @@
+use bevy::prelude::*;
+
+use crate::{
+    map::{GameMap, Position, TerrainType, Tile},
+    pieces::Occupier,
+    spells::TileEffect,
+    traps::{Trap, TrapBundle},
+};
+
+use super::{
+    orient_entity, Action, ActionError, ActionEvent, ActionOutcome, ActionPhase, ActionResult,
+};
+
+/// Apply a move's tile effect to a single tile, the neighbours are re-autotiled by the renderer
+#[derive(Clone, Debug)]
+pub struct ModifyTerrainAction {
+    pub instigator: Entity,
+    pub target: IVec2,
+    pub effect: TileEffect,
+}
+
+impl ModifyTerrainAction {
+    fn trap_at(&self, world: &mut World) -> Option<Entity> {
+        world
+            .query_filtered::<(Entity, &Position), With<Trap>>()
+            .iter(world)
+            .find(|(_, position)| position.0 == self.target)
+            .map(|(entity, _)| entity)
+    }
+}
+
+impl Action for ModifyTerrainAction {
+    fn execute(&self, world: &mut World) -> ActionResult {
+        self.can_execute(world)?;
+
+        match &self.effect {
+            TileEffect::ChangeTerrain { to, .. } => {
+                let Some(mut map) = world.get_resource_mut::<GameMap>() else {
+                    return Err(ActionError::TargetMissing);
+                };
+
+                if let Some(terrain_data) = map.tiles.get_mut(&self.target) {
+                    terrain_data.r#type = *to;
+                }
+
+                let Some(target_tile) = map.tiles_lookup.get(&self.target).cloned() else {
+                    return Err(ActionError::TargetMissing);
+                };
+
+                if let Ok(mut tile) = world.query::<&mut Tile>().get_mut(world, target_tile) {
+                    tile.0.r#type = *to;
+                }
+            }
+            TileEffect::SetTrap(kind) => {
+                world.spawn(TrapBundle::new(*kind, self.target));
+            }
+            TileEffect::RemoveTrap => {
+                if let Some(trap) = self.trap_at(world) {
+                    world.despawn(trap);
+                }
+            }
+        }
+
+        orient_entity(world, self.instigator, self.target);
+
+        Ok(ActionOutcome::done())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn event(&self) -> ActionEvent {
+        ActionEvent::ModifyTerrain(self.clone())
+    }
+
+    fn phase(&self) -> ActionPhase {
+        ActionPhase::Sequential
+    }
+
+    fn can_execute(&self, world: &mut World) -> Result<(), ActionError> {
+        let Some(board) = world.get_resource::<GameMap>() else {
+            return Err(ActionError::TargetMissing);
+        };
+
+        // check if the targeted position is on the board
+        let Some(tile) = board.tiles.get(&self.target) else {
+            return Err(ActionError::TargetMissing);
+        };
+        let terrain = tile.r#type;
+
+        match &self.effect {
+            TileEffect::ChangeTerrain { from, to } => {
+                if terrain == *to || !from.contains(&terrain) {
+                    return Err(ActionError::NoEffect);
+                }
+
+                // Nothing can stand in a wall
+                if *to == TerrainType::Wall
+                    && world
+                        .query_filtered::<&Position, With<Occupier>>()
+                        .iter(world)
+                        .any(|p| p.0 == self.target)
+                {
+                    return Err(ActionError::Occupied);
+                }
+            }
+            TileEffect::SetTrap(_) => {
+                if terrain != TerrainType::Ground || self.trap_at(world).is_some() {
+                    return Err(ActionError::NoEffect);
+                }
+            }
+            TileEffect::RemoveTrap => {
+                if self.trap_at(world).is_none() {
+                    return Err(ActionError::NoEffect);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use crate::{
+        actions::ActionError,
+        faction::Faction,
+        map::{GameMap, TerrainType, Tile},
+        spells::TileEffect,
+        test_harness::TestWorld,
+        traps::{Trap, TrapKind},
+    };
+
+    use super::ModifyTerrainAction;
+
+    fn shatter_walls() -> TileEffect {
+        TileEffect::ChangeTerrain {
+            from: vec![TerrainType::Wall],
+            to: TerrainType::Ground,
+        }
+    }
+
+    #[test]
+    fn turns_a_wall_into_ground() {
+        let wall = IVec2::new(2, 1);
+        let mut test_world = TestWorld::new(TestWorld::map(5, 5, &[wall]));
+        let instigator = test_world.spawn_piece(IVec2::new(1, 1), Faction::Player, 10);
+
+        test_world.enqueue(
+            instigator,
+            vec![Box::new(ModifyTerrainAction {
+                instigator,
+                target: wall,
+                effect: shatter_walls(),
+            })],
+        );
+        test_world.run();
+
+        assert_eq!(test_world.terrain(wall), TerrainType::Ground);
+        let tile = test_world.world.resource::<GameMap>().tiles_lookup[&wall];
+        assert_eq!(
+            test_world.world.get::<Tile>(tile).unwrap().0.r#type,
+            TerrainType::Ground
+        );
+        test_world.assert_executed::<ModifyTerrainAction>(1);
+    }
+
+    #[test]
+    fn cannot_shatter_ground() {
+        let mut test_world = TestWorld::new(TestWorld::map(5, 5, &[]));
+        let instigator = test_world.spawn_piece(IVec2::new(1, 1), Faction::Player, 10);
+
+        test_world.enqueue(
+            instigator,
+            vec![Box::new(ModifyTerrainAction {
+                instigator,
+                target: IVec2::new(2, 1),
+                effect: shatter_walls(),
+            })],
+        );
+        test_world.run();
+
+        test_world.assert_executed::<ModifyTerrainAction>(0);
+        test_world.assert_rejected::<ModifyTerrainAction>(ActionError::NoEffect);
+    }
+
+    #[test]
+    fn sets_then_removes_a_trap() {
+        let target = IVec2::new(2, 1);
+        let mut test_world = TestWorld::new(TestWorld::map(5, 5, &[]));
+        let instigator = test_world.spawn_piece(IVec2::new(1, 1), Faction::Player, 10);
+
+        for effect in [
+            TileEffect::SetTrap(TrapKind::Spike),
+            TileEffect::SetTrap(TrapKind::Spike),
+        ] {
+            test_world.enqueue(
+                instigator,
+                vec![Box::new(ModifyTerrainAction {
+                    instigator,
+                    target,
+                    effect,
+                })],
+            );
+            test_world.run();
+        }
+
+        let mut traps = test_world.world.query::<&Trap>();
+        assert_eq!(traps.iter(&test_world.world).count(), 1);
+        test_world.assert_rejected::<ModifyTerrainAction>(ActionError::NoEffect);
+
+        test_world.enqueue(
+            instigator,
+            vec![Box::new(ModifyTerrainAction {
+                instigator,
+                target,
+                effect: TileEffect::RemoveTrap,
+            })],
+        );
+        test_world.run();
+
+        assert_eq!(traps.iter(&test_world.world).count(), 0);
+        test_world.assert_executed::<ModifyTerrainAction>(2);
+    }
+}
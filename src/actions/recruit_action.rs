@@ -0,0 +1,58 @@
+use bevy::prelude::*;
+
+use crate::{faction::Faction, map::Position, recruitment::RecruitmentPrompt, stats::Health};
+
+use super::{
+    orient_entity, Action, ActionError, ActionEvent, ActionOutcome, ActionPhase, ActionResult,
+};
+
+#[derive(Debug, Clone)]
+pub struct RecruitAction {
+    pub recruiter: Entity,
+    pub target: Entity,
+}
+
+impl Action for RecruitAction {
+    fn execute(&self, world: &mut World) -> ActionResult {
+        self.can_execute(world)?;
+
+        let mut health = world
+            .get_mut::<Health>(self.target)
+            .ok_or(ActionError::TargetMissing)?;
+        health.value = health.max;
+
+        world.resource_mut::<RecruitmentPrompt>().recruit = Some(self.target);
+
+        let recruiter_position = world
+            .get::<Position>(self.recruiter)
+            .ok_or(ActionError::TargetMissing)?
+            .0;
+        orient_entity(world, self.target, recruiter_position);
+
+        Ok(ActionOutcome::done())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn event(&self) -> ActionEvent {
+        ActionEvent::Recruit(self.clone())
+    }
+
+    fn phase(&self) -> ActionPhase {
+        ActionPhase::Sequential
+    }
+
+    fn can_execute(&self, world: &mut World) -> Result<(), ActionError> {
+        if world.get::<Health>(self.target).is_none() {
+            return Err(ActionError::TargetMissing);
+        }
+        if world.resource::<RecruitmentPrompt>().is_pending()
+            || world.get::<Faction>(self.target) != Some(&Faction::Foe)
+        {
+            return Err(ActionError::NoEffect);
+        }
+        Ok(())
+    }
+}
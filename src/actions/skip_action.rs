@@ -1,24 +1,59 @@
 use bevy::prelude::*;
 
-use super::Action;
+use crate::{
+    map::Position,
+    player::Player,
+    traps::{HiddenTrap, Trap},
+};
+
+use super::{Action, ActionError, ActionEvent, ActionOutcome, ActionPhase, ActionResult};
 
 #[derive(Debug, Clone)]
-pub struct SkipAction;
+pub struct SkipAction {
+    pub entity: Entity,
+}
 
 impl Action for SkipAction {
-    fn execute(&self, _world: &mut World) -> Result<Vec<Box<dyn Action>>, ()> {
-        Ok(Vec::new())
+    fn execute(&self, world: &mut World) -> ActionResult {
+        // The player searches the tiles around when skipping its turn
+        if world.get::<Player>(self.entity).is_some() {
+            reveal_traps_around(world, self.entity);
+        }
+
+        Ok(ActionOutcome::done())
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
 
-    fn is_parallel_execution(&self) -> bool {
-        true
+    fn event(&self) -> ActionEvent {
+        ActionEvent::Skip(self.clone())
+    }
+
+    fn phase(&self) -> ActionPhase {
+        ActionPhase::Movement
     }
 
-    fn can_execute(&self, _world: &mut World) -> bool {
-        true
+    fn can_execute(&self, _world: &mut World) -> Result<(), ActionError> {
+        Ok(())
+    }
+}
+
+fn reveal_traps_around(world: &mut World, entity: Entity) {
+    let Some(position) = world.get::<Position>(entity).map(|p| p.0) else {
+        return;
+    };
+
+    let hidden_traps = world
+        .query_filtered::<(Entity, &Position), (With<Trap>, With<HiddenTrap>)>()
+        .iter(world)
+        .filter(|(_, p)| (p.0 - position).abs().max_element() <= 1)
+        .map(|(trap, _)| trap)
+        .collect::<Vec<_>>();
+
+    for trap in hidden_traps {
+        info!("Trap revealed {:?}", trap);
+        world.entity_mut(trap).remove::<HiddenTrap>();
     }
 }
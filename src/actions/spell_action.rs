@@ -1,14 +1,11 @@
 use bevy::prelude::*;
 
-use crate::{
-    ivec2::OrientationExt,
-    map::Position,
-    pieces::FacingOrientation,
-    spells::{Spell, SpellType},
-    stats::{Health, Stats},
-};
+use crate::spells::Spell;
 
-use super::{orient_entity, spell_projectile_action::SpellProjectileAction, Action};
+use super::{
+    spell_strike_action::SpellStrikeAction, Action, ActionError, ActionEvent, ActionOutcome,
+    ActionPhase, ActionResult,
+};
 
 #[derive(Debug, Clone)]
 pub struct SpellAction {
@@ -17,65 +14,307 @@ pub struct SpellAction {
 }
 
 impl Action for SpellAction {
-    fn execute(&self, world: &mut World) -> Result<Vec<Box<dyn Action>>, ()> {
-        if !self.can_execute(world) {
-            return Err(());
-        };
+    fn execute(&self, world: &mut World) -> ActionResult {
+        self.can_execute(world)?;
 
-        let world: &mut World = world;
+        // Each strike is traced when it is fired, the previous ones may have moved or knocked out
+        // the pieces on its way
+        let strikes = (1..=self.spell.strikes.max(1))
+            .map(|strike| {
+                Box::new(SpellStrikeAction {
+                    caster: self.caster,
+                    spell: self.spell.clone(),
+                    strike,
+                }) as Box<dyn Action>
+            })
+            .collect();
+        Ok(ActionOutcome::then_each(strikes))
+    }
 
-        let Ok((facing_orientation, position)) = world
-            .query::<(&FacingOrientation, &Position)>()
-            .get(world, self.caster)
-        else {
-            return Err(());
-        };
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn event(&self) -> ActionEvent {
+        ActionEvent::Spell(self.clone())
+    }
 
-        let direction_vector = facing_orientation.0.to_vector();
-        let position_vector = position.0;
+    fn phase(&self) -> ActionPhase {
+        ActionPhase::Sequential
+    }
 
-        let direction = direction_vector + position_vector;
-        orient_entity(world, self.caster, direction);
+    fn can_execute(&self, _world: &mut World) -> Result<(), ActionError> {
+        Ok(())
+    }
+}
 
-        let mut target: IVec2 = direction_vector * *self.spell.range.end() + position_vector;
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use char_animation::anim_key::AnimKey;
 
-        // Looks for any target in range of the spell
-        for i in self.spell.range.clone() {
-            let test_position = direction_vector * i + position_vector;
+    use crate::{
+        actions::{
+            damage_action::DamageAction, modify_terrain_action::ModifyTerrainAction,
+            spell_hit_action::SpellHitAction, spell_projectile_action::SpellProjectileAction,
+            spell_strike_action::SpellStrikeAction,
+        },
+        faction::Faction,
+        map::TerrainType,
+        move_type::MoveCategory,
+        spells::{
+            ProjectileRays, ProjectileSpell, Spell, SpellCast, SpellHit, SpellType, TileEffect,
+        },
+        test_harness::TestWorld,
+    };
 
-            let targetable_entities = world
-                .query_filtered::<(Entity, &Position), With<Health>>()
-                .iter(world)
-                .filter(|(_, p)| p.0 == test_position)
-                .collect::<Vec<_>>();
+    use super::SpellAction;
 
-            if targetable_entities.is_empty() {
-                continue;
-            }
-            target = test_position;
-            break;
+    fn projectile() -> ProjectileSpell {
+        ProjectileSpell {
+            visual_effect: String::new(),
+            rays: ProjectileRays::One,
+            stop_at_wall: true,
+            stop_at_hit: true,
+            boomerang: false,
         }
+    }
 
-        match &self.spell.spell_type {
-            SpellType::Projectile(projectile_spell) => Ok(vec![Box::new(SpellProjectileAction {
-                caster: self.caster,
-                projectile: projectile_spell.clone(),
-                spell: self.spell.clone(),
-                target,
-            })]),
-            _ => Ok(vec![]),
+    fn projectile_spell(damage: i32, projectile: ProjectileSpell) -> Spell {
+        Spell {
+            name: "Test spell".to_string(),
+            range: 1..=3,
+            spell_type: SpellType::Projectile(projectile),
+            hit: SpellHit {
+                visual_effect: String::new(),
+                damage,
+                move_type: MoveCategory::Special,
+                tile_effects: vec![],
+            },
+            cast: SpellCast {
+                visual_effect: String::new(),
+                animation: AnimKey::Shoot,
+            },
+            strikes: 1,
+            explosion: None,
         }
     }
 
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
+    #[test]
+    fn projectile_hits_the_first_target_in_range() {
+        let mut test_world = TestWorld::new(TestWorld::map(5, 5, &[]));
+        // Facing south
+        let caster = test_world.spawn_piece(IVec2::new(2, 4), Faction::Player, 10);
+        let target = test_world.spawn_piece(IVec2::new(2, 2), Faction::Foe, 10);
+        let behind = test_world.spawn_piece(IVec2::new(2, 1), Faction::Foe, 10);
+
+        test_world.enqueue(
+            caster,
+            vec![Box::new(SpellAction {
+                caster,
+                spell: projectile_spell(4, projectile()),
+            })],
+        );
+        test_world.run();
+
+        test_world.assert_health(target, 6);
+        test_world.assert_health(behind, 10);
+        test_world.assert_executed::<SpellProjectileAction>(1);
+        test_world.assert_executed::<SpellHitAction>(1);
+        test_world.assert_executed::<DamageAction>(1);
+        assert_eq!(
+            test_world.executed::<SpellProjectileAction>()[0].target,
+            IVec2::new(2, 2)
+        );
     }
 
-    fn is_parallel_execution(&self) -> bool {
-        false
+    #[test]
+    fn projectile_without_target_deals_no_damage() {
+        let mut test_world = TestWorld::new(TestWorld::map(5, 5, &[]));
+        let caster = test_world.spawn_piece(IVec2::new(2, 4), Faction::Player, 10);
+        let out_of_range = test_world.spawn_piece(IVec2::new(2, 0), Faction::Foe, 10);
+
+        test_world.enqueue(
+            caster,
+            vec![Box::new(SpellAction {
+                caster,
+                spell: projectile_spell(4, projectile()),
+            })],
+        );
+        test_world.run();
+
+        test_world.assert_health(out_of_range, 10);
+        test_world.assert_executed::<SpellProjectileAction>(1);
+        test_world.assert_executed::<DamageAction>(0);
     }
 
-    fn can_execute(&self, _world: &mut World) -> bool {
-        true
+    #[test]
+    fn projectile_stops_at_walls() {
+        let wall = IVec2::new(2, 3);
+        let mut test_world = TestWorld::new(TestWorld::map(5, 5, &[wall]));
+        let caster = test_world.spawn_piece(IVec2::new(2, 4), Faction::Player, 10);
+        let behind_wall = test_world.spawn_piece(IVec2::new(2, 2), Faction::Foe, 10);
+
+        test_world.enqueue(
+            caster,
+            vec![Box::new(SpellAction {
+                caster,
+                spell: projectile_spell(4, projectile()),
+            })],
+        );
+        test_world.run();
+
+        test_world.assert_health(behind_wall, 10);
+        test_world.assert_executed::<DamageAction>(0);
+        assert_eq!(
+            test_world.executed::<SpellProjectileAction>()[0].target,
+            IVec2::new(2, 4)
+        );
+    }
+
+    #[test]
+    fn projectile_shatters_the_wall_it_stops_at() {
+        let wall = IVec2::new(2, 3);
+        let mut test_world = TestWorld::new(TestWorld::map(5, 5, &[wall, IVec2::new(2, 1)]));
+        let caster = test_world.spawn_piece(IVec2::new(2, 4), Faction::Player, 10);
+
+        let mut spell = projectile_spell(4, projectile());
+        spell.hit.tile_effects = vec![TileEffect::ChangeTerrain {
+            from: vec![TerrainType::Wall],
+            to: TerrainType::Ground,
+        }];
+        test_world.enqueue(caster, vec![Box::new(SpellAction { caster, spell })]);
+        test_world.run();
+
+        assert_eq!(test_world.terrain(wall), TerrainType::Ground);
+        assert_eq!(test_world.terrain(IVec2::new(2, 1)), TerrainType::Wall);
+        test_world.assert_executed::<ModifyTerrainAction>(1);
+        assert!(test_world.rejections::<ModifyTerrainAction>().is_empty());
+    }
+
+    #[test]
+    fn piercing_projectile_flies_through_walls_and_targets() {
+        let mut test_world = TestWorld::new(TestWorld::map(5, 5, &[IVec2::new(2, 3)]));
+        let caster = test_world.spawn_piece(IVec2::new(2, 4), Faction::Player, 10);
+        let first = test_world.spawn_piece(IVec2::new(2, 2), Faction::Foe, 10);
+        let second = test_world.spawn_piece(IVec2::new(2, 1), Faction::Foe, 10);
+
+        let projectile = ProjectileSpell {
+            stop_at_wall: false,
+            stop_at_hit: false,
+            ..projectile()
+        };
+        test_world.enqueue(
+            caster,
+            vec![Box::new(SpellAction {
+                caster,
+                spell: projectile_spell(4, projectile),
+            })],
+        );
+        test_world.run();
+
+        test_world.assert_health(first, 6);
+        test_world.assert_health(second, 6);
+        test_world.assert_executed::<SpellHitAction>(2);
+        test_world.assert_executed::<DamageAction>(2);
+    }
+
+    #[test]
+    fn boomerang_hits_again_on_the_way_back() {
+        let mut test_world = TestWorld::new(TestWorld::map(5, 5, &[]));
+        let caster = test_world.spawn_piece(IVec2::new(2, 4), Faction::Player, 10);
+        let target = test_world.spawn_piece(IVec2::new(2, 3), Faction::Foe, 10);
+
+        let projectile = ProjectileSpell {
+            stop_at_hit: false,
+            boomerang: true,
+            ..projectile()
+        };
+        test_world.enqueue(
+            caster,
+            vec![Box::new(SpellAction {
+                caster,
+                spell: projectile_spell(4, projectile),
+            })],
+        );
+        test_world.run();
+
+        test_world.assert_health(target, 2);
+        test_world.assert_executed::<SpellProjectileAction>(1);
+        test_world.assert_executed::<SpellHitAction>(2);
+        assert_eq!(
+            test_world.executed::<SpellProjectileAction>()[0].target,
+            IVec2::new(2, 1)
+        );
+    }
+
+    #[test]
+    fn spread_fires_one_projectile_per_ray() {
+        let mut test_world = TestWorld::new(TestWorld::map(5, 5, &[]));
+        // Facing south
+        let caster = test_world.spawn_piece(IVec2::new(2, 4), Faction::Player, 10);
+        let front = test_world.spawn_piece(IVec2::new(2, 3), Faction::Foe, 10);
+        let diagonal = test_world.spawn_piece(IVec2::new(0, 2), Faction::Foe, 10);
+        let side = test_world.spawn_piece(IVec2::new(3, 4), Faction::Foe, 10);
+
+        let projectile = ProjectileSpell {
+            rays: ProjectileRays::Three,
+            ..projectile()
+        };
+        test_world.enqueue(
+            caster,
+            vec![Box::new(SpellAction {
+                caster,
+                spell: projectile_spell(4, projectile),
+            })],
+        );
+        test_world.run();
+
+        test_world.assert_executed::<SpellProjectileAction>(3);
+        test_world.assert_health(front, 6);
+        test_world.assert_health(diagonal, 6);
+        test_world.assert_health(side, 10);
+    }
+
+    #[test]
+    fn multi_strike_hits_several_times() {
+        let mut test_world = TestWorld::new(TestWorld::map(5, 5, &[]));
+        let caster = test_world.spawn_piece(IVec2::new(2, 4), Faction::Player, 10);
+        let target = test_world.spawn_piece(IVec2::new(2, 3), Faction::Foe, 10);
+
+        let spell = Spell {
+            strikes: 3,
+            ..projectile_spell(2, projectile())
+        };
+        test_world.enqueue(caster, vec![Box::new(SpellAction { caster, spell })]);
+        test_world.run();
+
+        test_world.assert_health(target, 4);
+        test_world.assert_executed::<SpellProjectileAction>(3);
+        let strikes = test_world
+            .executed::<SpellStrikeAction>()
+            .iter()
+            .map(|strike| strike.strike)
+            .collect::<Vec<_>>();
+        assert_eq!(strikes, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn next_strike_skips_the_knocked_out_target() {
+        let mut test_world = TestWorld::new(TestWorld::map(5, 5, &[]));
+        let caster = test_world.spawn_piece(IVec2::new(2, 4), Faction::Player, 10);
+        let target = test_world.spawn_piece(IVec2::new(2, 3), Faction::Foe, 2);
+        let behind = test_world.spawn_piece(IVec2::new(2, 2), Faction::Foe, 10);
+
+        let spell = Spell {
+            strikes: 2,
+            ..projectile_spell(2, projectile())
+        };
+        test_world.enqueue(caster, vec![Box::new(SpellAction { caster, spell })]);
+        test_world.run();
+
+        test_world.assert_health(target, 0);
+        test_world.assert_health(behind, 8);
+        test_world.assert_executed::<SpellHitAction>(2);
     }
 }
@@ -0,0 +1,170 @@
+use bevy::prelude::*;
+
+use crate::{
+    faction::Faction,
+    map::Position,
+    spells::{SpellExplosion, SpellHit},
+    stats::Health,
+};
+
+use super::{
+    spell_hit_action::SpellHitAction, spell_projectile_action::tile_actions, Action, ActionError,
+    ActionEvent, ActionOutcome, ActionPhase, ActionResult,
+};
+
+#[derive(Debug, Clone)]
+pub struct SpellExplosionAction {
+    pub caster: Entity,
+    pub hit: SpellHit,
+    pub explosion: SpellExplosion,
+    /// The impact tile
+    pub center: IVec2,
+}
+
+impl SpellExplosionAction {
+    pub fn is_in_range(&self, position: IVec2) -> bool {
+        (position - self.center).abs().max_element() <= self.explosion.range
+    }
+}
+
+impl Action for SpellExplosionAction {
+    fn execute(&self, world: &mut World) -> ActionResult {
+        self.can_execute(world)?;
+
+        let caster_faction = world
+            .get::<Faction>(self.caster)
+            .copied()
+            .unwrap_or(Faction::None);
+
+        let mut targets = world
+            .query_filtered::<(Entity, &Position, Option<&Faction>), With<Health>>()
+            .iter(world)
+            .filter(|(entity, position, faction)| {
+                self.is_in_range(position.0)
+                    && self.explosion.targets.allows(
+                        *entity == self.caster,
+                        &caster_faction,
+                        faction.unwrap_or(&Faction::None),
+                    )
+            })
+            .map(|(entity, position, _)| (entity, position.0))
+            .collect::<Vec<_>>();
+        // The query iteration order is not meaningful
+        targets.sort_by_key(|(_, position)| (position.y, position.x));
+
+        let mut hits = targets
+            .into_iter()
+            .map(|(target, _)| {
+                Box::new(SpellHitAction {
+                    caster: self.caster,
+                    target,
+                    hit: self.hit.clone(),
+                }) as Box<dyn Action>
+            })
+            .collect::<Vec<_>>();
+
+        let range = self.explosion.range;
+        let tiles = (-range..=range)
+            .flat_map(|y| (-range..=range).map(move |x| IVec2::new(x, y)))
+            .map(|offset| self.center + offset);
+        hits.extend(tile_actions(world, self.caster, &self.hit, tiles));
+
+        Ok(ActionOutcome::then_each(hits))
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn event(&self) -> ActionEvent {
+        ActionEvent::SpellExplosion(self.clone())
+    }
+
+    fn phase(&self) -> ActionPhase {
+        ActionPhase::Sequential
+    }
+
+    fn can_execute(&self, _world: &mut World) -> Result<(), ActionError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use crate::{
+        actions::spell_hit_action::SpellHitAction,
+        faction::Faction,
+        move_type::MoveCategory,
+        spells::{SpellExplosion, SpellHit, SpellTargets},
+        test_harness::TestWorld,
+    };
+
+    use super::SpellExplosionAction;
+
+    fn explosion(caster: Entity, center: IVec2, targets: SpellTargets) -> SpellExplosionAction {
+        SpellExplosionAction {
+            caster,
+            hit: SpellHit {
+                visual_effect: String::new(),
+                damage: 3,
+                move_type: MoveCategory::Special,
+                tile_effects: vec![],
+            },
+            explosion: SpellExplosion {
+                range: 1,
+                targets,
+                visual_effect: String::new(),
+            },
+            center,
+        }
+    }
+
+    #[test]
+    fn hits_every_foe_around_the_impact() {
+        let mut test_world = TestWorld::new(TestWorld::map(7, 7, &[]));
+        let caster = test_world.spawn_piece(IVec2::new(3, 1), Faction::Player, 10);
+        let center = test_world.spawn_piece(IVec2::new(3, 3), Faction::Foe, 10);
+        let diagonal = test_world.spawn_piece(IVec2::new(4, 4), Faction::Foe, 10);
+        let out_of_range = test_world.spawn_piece(IVec2::new(3, 5), Faction::Foe, 10);
+        let ally = test_world.spawn_piece(IVec2::new(2, 3), Faction::Friend, 10);
+
+        test_world.enqueue(
+            caster,
+            vec![Box::new(explosion(
+                caster,
+                IVec2::new(3, 3),
+                SpellTargets::FOES,
+            ))],
+        );
+        test_world.run();
+
+        test_world.assert_health(center, 7);
+        test_world.assert_health(diagonal, 7);
+        test_world.assert_health(out_of_range, 10);
+        test_world.assert_health(ally, 10);
+        test_world.assert_executed::<SpellHitAction>(2);
+    }
+
+    #[test]
+    fn can_hit_the_caster() {
+        let mut test_world = TestWorld::new(TestWorld::map(5, 5, &[]));
+        let caster = test_world.spawn_piece(IVec2::new(2, 2), Faction::Player, 10);
+        let foe = test_world.spawn_piece(IVec2::new(2, 3), Faction::Foe, 10);
+
+        let targets = SpellTargets {
+            caster: true,
+            allies: false,
+            foes: false,
+        };
+        test_world.enqueue(
+            caster,
+            vec![Box::new(explosion(caster, IVec2::new(2, 2), targets))],
+        );
+        test_world.run();
+
+        test_world.assert_health(caster, 7);
+        test_world.assert_health(foe, 10);
+    }
+}
@@ -2,7 +2,10 @@ use bevy::prelude::*;
 
 use crate::spells::SpellHit;
 
-use super::{damage_action::DamageAction, Action};
+use super::{
+    damage_action::DamageAction, Action, ActionError, ActionEvent, ActionOutcome, ActionPhase,
+    ActionResult,
+};
 
 #[derive(Debug, Clone)]
 pub struct SpellHitAction {
@@ -12,28 +15,30 @@ pub struct SpellHitAction {
 }
 
 impl Action for SpellHitAction {
-    fn execute(&self, world: &mut World) -> Result<Vec<Box<dyn Action>>, ()> {
-        if !self.can_execute(world) {
-            return Err(());
-        };
+    fn execute(&self, world: &mut World) -> ActionResult {
+        self.can_execute(world)?;
 
-        Ok(vec![Box::new(DamageAction {
+        Ok(ActionOutcome::then(vec![Box::new(DamageAction {
             attacker: self.caster,
             target: self.target,
             value: self.hit.damage,
             move_type: self.hit.move_type.clone(),
-        })])
+        })]))
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
 
-    fn is_parallel_execution(&self) -> bool {
-        false
+    fn event(&self) -> ActionEvent {
+        ActionEvent::SpellHit(self.clone())
     }
 
-    fn can_execute(&self, _world: &mut World) -> bool {
-        true
+    fn phase(&self) -> ActionPhase {
+        ActionPhase::Sequential
+    }
+
+    fn can_execute(&self, _world: &mut World) -> Result<(), ActionError> {
+        Ok(())
     }
 }
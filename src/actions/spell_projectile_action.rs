@@ -1,62 +1,111 @@
 use bevy::prelude::*;
 
 use crate::{
-    map::Position,
-    spells::{ProjectileSpell, Spell},
-    stats::{Health, Stats},
+    spells::{ProjectileSpell, Spell, SpellHit},
+    stats::Health,
 };
 
-use super::{spell_hit_action::SpellHitAction, Action};
+use super::{
+    modify_terrain_action::ModifyTerrainAction, spell_explosion_action::SpellExplosionAction,
+    spell_hit_action::SpellHitAction, Action, ActionError, ActionEvent, ActionOutcome, ActionPhase,
+    ActionResult,
+};
 
 #[derive(Debug, Clone)]
 pub struct SpellProjectileAction {
     pub caster: Entity,
     pub spell: Spell,
     pub projectile: ProjectileSpell,
+    /// Where the projectile lands
     pub target: IVec2,
+    /// The pieces hit on the way, in hit order
+    pub hits: Vec<Entity>,
+    /// The tiles hit on the way, where the tile effects of the spell apply
+    pub tiles: Vec<IVec2>,
 }
 
 impl Action for SpellProjectileAction {
-    fn execute(&self, world: &mut World) -> Result<Vec<Box<dyn Action>>, ()> {
-        if !self.can_execute(world) {
-            return Err(());
-        };
-
-        let target_entities = world
-            .query_filtered::<(Entity, &Position), With<Health>>()
-            .iter(world)
-            .filter(|(_, p)| p.0 == self.target)
-            .collect::<Vec<_>>();
+    fn execute(&self, world: &mut World) -> ActionResult {
+        self.can_execute(world)?;
 
-        if target_entities.is_empty() {
-            // return error there if we dont want to play the projectile animation
-            //return Err(());
-            return Ok(vec![]);
+        // The explosion hits the pieces around the impact instead
+        if let Some(explosion) = &self.spell.explosion {
+            return Ok(ActionOutcome::then_each(vec![Box::new(
+                SpellExplosionAction {
+                    caster: self.caster,
+                    hit: self.spell.hit.clone(),
+                    explosion: explosion.clone(),
+                    center: self.target,
+                },
+            )]));
         }
 
-        let result = target_entities
+        // The projectile is still played when there is nothing to hit
+        let mut result = self
+            .hits
             .iter()
+            .filter(|target| {
+                world
+                    .get::<Health>(**target)
+                    .is_some_and(|health| !health.is_dead())
+            })
             .map(|target| {
                 Box::new(SpellHitAction {
                     caster: self.caster,
-                    target: target.0,
+                    target: *target,
                     hit: self.spell.hit.clone(),
                 }) as Box<dyn Action>
             })
             .collect::<Vec<_>>();
+        result.extend(tile_actions(
+            world,
+            self.caster,
+            &self.spell.hit,
+            self.tiles.iter().copied(),
+        ));
 
-        Ok(result)
+        Ok(ActionOutcome::then_each(result))
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
 
-    fn is_parallel_execution(&self) -> bool {
-        false
+    fn event(&self) -> ActionEvent {
+        ActionEvent::SpellProjectile(self.clone())
     }
 
-    fn can_execute(&self, _world: &mut World) -> bool {
-        true
+    fn phase(&self) -> ActionPhase {
+        ActionPhase::Sequential
     }
+
+    fn can_execute(&self, _world: &mut World) -> Result<(), ActionError> {
+        Ok(())
+    }
+}
+
+/// The tile effects of a hit that do something, so that no rejection is reported for the others
+pub(crate) fn tile_actions(
+    world: &mut World,
+    caster: Entity,
+    hit: &SpellHit,
+    tiles: impl Iterator<Item = IVec2>,
+) -> Vec<Box<dyn Action>> {
+    tiles
+        .flat_map(|target| {
+            hit.tile_effects
+                .iter()
+                .map(move |effect| ModifyTerrainAction {
+                    instigator: caster,
+                    target,
+                    effect: effect.clone(),
+                })
+        })
+        .filter_map(|action| {
+            action
+                .can_execute(world)
+                .is_ok()
+                .then(|| Box::new(action) as Box<dyn Action>)
+        })
+        .collect()
 }
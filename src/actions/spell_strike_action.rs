@@ -0,0 +1,183 @@
+use bevy::prelude::*;
+
+use crate::{
+    map::{GameMap, Position, TerrainType},
+    pieces::FacingOrientation,
+    spells::{ProjectileSpell, Spell, SpellType},
+    stats::Health,
+};
+
+use super::{
+    spell_projectile_action::SpellProjectileAction, Action, ActionError, ActionEvent,
+    ActionOutcome, ActionPhase, ActionResult,
+};
+
+/// One of the `strikes` of a spell, its projectiles are traced when it is fired
+#[derive(Debug, Clone)]
+pub struct SpellStrikeAction {
+    pub caster: Entity,
+    pub spell: Spell,
+    /// Starts at 1, up to the `strikes` of the spell
+    pub strike: u32,
+}
+
+impl Action for SpellStrikeAction {
+    fn execute(&self, world: &mut World) -> ActionResult {
+        self.can_execute(world)?;
+
+        let Ok((facing_orientation, position)) = world
+            .query::<(&FacingOrientation, &Position)>()
+            .get(world, self.caster)
+        else {
+            return Err(ActionError::TargetMissing);
+        };
+
+        let orientation = facing_orientation.0.clone();
+        let position = position.0;
+
+        match &self.spell.spell_type {
+            SpellType::Projectile(projectile_spell) => {
+                // Each ray is fired one after the other
+                let projectiles = projectile_spell
+                    .rays
+                    .directions(&orientation)
+                    .into_iter()
+                    .map(|direction| {
+                        let ProjectilePath {
+                            target,
+                            hits,
+                            tiles,
+                        } = trace_projectile(
+                            world,
+                            position,
+                            direction,
+                            &self.spell,
+                            projectile_spell,
+                        );
+                        Box::new(SpellProjectileAction {
+                            caster: self.caster,
+                            projectile: projectile_spell.clone(),
+                            spell: self.spell.clone(),
+                            target,
+                            hits,
+                            tiles,
+                        }) as Box<dyn Action>
+                    })
+                    .collect();
+                Ok(ActionOutcome::then_each(projectiles))
+            }
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn event(&self) -> ActionEvent {
+        ActionEvent::SpellStrike(self.clone())
+    }
+
+    fn phase(&self) -> ActionPhase {
+        ActionPhase::Sequential
+    }
+
+    fn can_execute(&self, _world: &mut World) -> Result<(), ActionError> {
+        Ok(())
+    }
+}
+
+struct ProjectilePath {
+    /// Where the projectile lands
+    target: IVec2,
+    /// The pieces hit on the way, in hit order, the boomerangs hit them again on the way back
+    hits: Vec<Entity>,
+    /// The tiles in range on the way, including the wall it stopped at
+    tiles: Vec<IVec2>,
+}
+
+/// Follow the projectile tile by tile from `origin`, returns where it lands and what it hits
+fn trace_projectile(
+    world: &mut World,
+    origin: IVec2,
+    direction: IVec2,
+    spell: &Spell,
+    projectile: &ProjectileSpell,
+) -> ProjectilePath {
+    let targetable_entities = world
+        .query::<(Entity, &Position, &Health)>()
+        .iter(world)
+        .filter(|(_, _, health)| !health.is_dead())
+        .map(|(entity, position, _)| (entity, position.0))
+        .collect::<Vec<_>>();
+    let map = world.resource::<GameMap>();
+    let hits_at = |position: IVec2| {
+        targetable_entities
+            .iter()
+            .filter(move |(_, p)| *p == position)
+            .map(|(entity, _)| *entity)
+            .collect::<Vec<_>>()
+    };
+
+    let mut target = origin;
+    let mut hits = vec![];
+    let mut tiles = vec![];
+    for i in 1..=*spell.range.end() {
+        let position = origin + direction * i;
+        let Some(tile) = map.tiles.get(&position) else {
+            break;
+        };
+        let in_range = spell.range.contains(&i);
+        if tile.r#type == TerrainType::Wall && projectile.stop_at_wall {
+            // The wall itself is still hit, e.g. to be shattered
+            if in_range {
+                tiles.push(position);
+            }
+            break;
+        }
+        target = position;
+
+        if !in_range {
+            continue;
+        }
+        tiles.push(position);
+        let tile_hits = hits_at(position);
+        if tile_hits.is_empty() {
+            continue;
+        }
+        hits.extend(tile_hits);
+        if projectile.stop_at_hit {
+            break;
+        }
+    }
+
+    // The boomerang flies back from where it landed to the caster, under the same rules
+    if projectile.boomerang {
+        let distance = (target - origin).abs().max_element();
+        for i in (1..distance).rev() {
+            let position = origin + direction * i;
+            let Some(tile) = map.tiles.get(&position) else {
+                break;
+            };
+            if tile.r#type == TerrainType::Wall && projectile.stop_at_wall {
+                break;
+            }
+            if !spell.range.contains(&i) {
+                continue;
+            }
+            let tile_hits = hits_at(position);
+            if tile_hits.is_empty() {
+                continue;
+            }
+            hits.extend(tile_hits);
+            if projectile.stop_at_hit {
+                break;
+            }
+        }
+    }
+
+    ProjectilePath {
+        target,
+        hits,
+        tiles,
+    }
+}
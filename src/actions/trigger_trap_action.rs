@@ -0,0 +1,159 @@
+use bevy::prelude::*;
+use rand::seq::IteratorRandom;
+
+use crate::{
+    ai::NPCBundle,
+    faction::Faction,
+    ivec2::IVec2Ext,
+    map::{GameMap, Position, TerrainType},
+    move_type::MoveCategory,
+    pieces::Occupier,
+    rng::{GameRng, RngStream},
+    status::{Immobilized, Poisoned},
+    traps::{HiddenTrap, Trap, TrapKind},
+};
+
+use super::{
+    damage_action::DamageAction, Action, ActionError, ActionEvent, ActionOutcome, ActionPhase,
+    ActionResult,
+};
+
+const SPIKE_DAMAGE: i32 = 5;
+const POISON_TURNS: u32 = 5;
+const STICKY_TURNS: u32 = 3;
+const TRIP_TURNS: u32 = 1;
+const SUMMON_COUNT: usize = 2;
+
+#[derive(Debug, Clone)]
+pub struct TriggerTrapAction {
+    pub entity: Entity,
+    pub trap: Entity,
+    pub kind: TrapKind,
+}
+
+impl Action for TriggerTrapAction {
+    fn execute(&self, world: &mut World) -> ActionResult {
+        self.can_execute(world)?;
+
+        // A triggered trap is always revealed
+        world.entity_mut(self.trap).remove::<HiddenTrap>();
+
+        let position = world
+            .get::<Position>(self.entity)
+            .ok_or(ActionError::TargetMissing)?
+            .0;
+
+        let next_actions: Vec<Box<dyn Action>> = match self.kind {
+            TrapKind::Warp => {
+                let free_tiles = free_tiles(world, position, None);
+                let destination = free_tiles
+                    .into_iter()
+                    .choose(world.resource_mut::<GameRng>().stream(RngStream::Combat))
+                    .ok_or(ActionError::NoEffect)?;
+                let mut position = world
+                    .get_mut::<Position>(self.entity)
+                    .ok_or(ActionError::TargetMissing)?;
+                position.0 = destination;
+                vec![]
+            }
+            TrapKind::Spike => vec![Box::new(DamageAction {
+                attacker: self.entity,
+                target: self.entity,
+                value: SPIKE_DAMAGE,
+                move_type: MoveCategory::Physical,
+            })],
+            TrapKind::Poison => {
+                world.entity_mut(self.entity).insert(Poisoned {
+                    remaining_turns: POISON_TURNS,
+                });
+                vec![]
+            }
+            TrapKind::Sticky => {
+                world.entity_mut(self.entity).insert(Immobilized {
+                    remaining_turns: STICKY_TURNS,
+                });
+                vec![]
+            }
+            TrapKind::Trip => {
+                world.entity_mut(self.entity).insert(Immobilized {
+                    remaining_turns: TRIP_TURNS,
+                });
+                vec![]
+            }
+            TrapKind::Summon => {
+                let free_tiles = free_tiles(world, position, Some(1));
+                let summon_positions = free_tiles.into_iter().choose_multiple(
+                    world.resource_mut::<GameRng>().stream(RngStream::Combat),
+                    SUMMON_COUNT,
+                );
+                for summon_position in summon_positions {
+                    world.spawn(NPCBundle::new(
+                        "NPC".to_string(),
+                        summon_position,
+                        Faction::Foe,
+                    ));
+                }
+                vec![]
+            }
+        };
+
+        Ok(ActionOutcome::then(next_actions))
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn event(&self) -> ActionEvent {
+        ActionEvent::TriggerTrap(self.clone())
+    }
+
+    fn phase(&self) -> ActionPhase {
+        ActionPhase::Sequential
+    }
+
+    fn can_execute(&self, world: &mut World) -> Result<(), ActionError> {
+        world
+            .get::<Trap>(self.trap)
+            .ok_or(ActionError::TargetMissing)?;
+        let trap_position = world
+            .get::<Position>(self.trap)
+            .ok_or(ActionError::TargetMissing)?;
+        let position = world
+            .get::<Position>(self.entity)
+            .ok_or(ActionError::TargetMissing)?;
+
+        if trap_position.0 != position.0 {
+            return Err(ActionError::OutOfRange);
+        }
+        Ok(())
+    }
+}
+
+/// Ground tiles without any occupier, limited to the ones at `max_distance` of `origin` if any
+fn free_tiles(world: &mut World, origin: IVec2, max_distance: Option<i32>) -> Vec<IVec2> {
+    let occupied = world
+        .query_filtered::<&Position, With<Occupier>>()
+        .iter(world)
+        .map(|p| p.0)
+        .collect::<Vec<_>>();
+
+    let Some(map) = world.get_resource::<GameMap>() else {
+        return vec![];
+    };
+
+    let mut tiles = map
+        .tiles
+        .iter()
+        .filter(|(p, t)| {
+            t.r#type == TerrainType::Ground
+                && !occupied.contains(p)
+                && **p != origin
+                && max_distance.is_none_or(|d| p.manhattan(origin) <= d)
+        })
+        .map(|(p, _)| *p)
+        .collect::<Vec<_>>();
+    // The map iteration order is not deterministic
+    tiles.sort_by_key(|p| (p.x, p.y));
+    tiles
+}
@@ -3,9 +3,14 @@ use bevy::prelude::*;
 use crate::{
     map::{GameMap, Position, TerrainType},
     pieces::Occupier,
+    status::Immobilized,
+    traps::Trap,
 };
 
-use super::{orient_entity, Action};
+use super::{
+    orient_entity, trigger_trap_action::TriggerTrapAction, Action, ActionError, ActionEvent,
+    ActionOutcome, ActionPhase, ActionResult,
+};
 
 #[derive(Debug, Clone)]
 pub struct WalkAction {
@@ -15,40 +20,61 @@ pub struct WalkAction {
 }
 
 impl Action for WalkAction {
-    fn execute(&self, world: &mut World) -> Result<Vec<Box<dyn Action>>, ()> {
-        if !self.can_execute(world) {
-            return Err(());
-        };
+    fn execute(&self, world: &mut World) -> ActionResult {
+        self.can_execute(world)?;
 
         orient_entity(world, self.entity, self.to);
 
         // get the position of the entity
-        let mut position = world.get_mut::<Position>(self.entity).ok_or(())?;
+        let mut position = world
+            .get_mut::<Position>(self.entity)
+            .ok_or(ActionError::TargetMissing)?;
         position.0 = self.to;
 
-        Ok(Vec::new())
+        // Trigger the trap under the destination once the walk is done
+        let trap = world
+            .query::<(Entity, &Trap, &Position)>()
+            .iter(world)
+            .find(|(_, _, p)| p.0 == self.to)
+            .map(|(trap, t, _)| {
+                Box::new(TriggerTrapAction {
+                    entity: self.entity,
+                    trap,
+                    kind: t.kind,
+                }) as Box<dyn Action>
+            });
+
+        Ok(ActionOutcome::then(trap.into_iter().collect()))
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
 
-    fn is_parallel_execution(&self) -> bool {
-        true
+    fn event(&self) -> ActionEvent {
+        ActionEvent::Walk(self.clone())
     }
 
-    fn can_execute(&self, world: &mut World) -> bool {
+    fn phase(&self) -> ActionPhase {
+        ActionPhase::Movement
+    }
+
+    fn can_execute(&self, world: &mut World) -> Result<(), ActionError> {
+        if world.get::<Immobilized>(self.entity).is_some() {
+            return Err(ActionError::Immobilized);
+        }
+
         let Some(board) = world.get_resource::<GameMap>() else {
-            return false;
+            return Err(ActionError::BlockedByTerrain);
         };
 
         // check if the targeted position is on the board
         let Some(tile) = board.tiles.get(&self.to) else {
-            return false;
+            return Err(ActionError::BlockedByTerrain);
         };
 
         if tile.r#type != TerrainType::Ground {
-            return false;
+            return Err(ActionError::BlockedByTerrain);
         }
 
         if world
@@ -56,9 +82,61 @@ impl Action for WalkAction {
             .iter(world)
             .any(|p| p.0 == self.to)
         {
-            return false;
+            return Err(ActionError::Occupied);
         };
 
-        true
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use crate::{actions::ActionError, faction::Faction, test_harness::TestWorld};
+
+    use super::WalkAction;
+
+    fn walk(entity: Entity, from: IVec2, to: IVec2) -> Vec<Box<dyn super::Action>> {
+        vec![Box::new(WalkAction { entity, from, to })]
+    }
+
+    #[test]
+    fn walks_to_a_free_ground_tile() {
+        let mut test_world = TestWorld::new(TestWorld::map(5, 5, &[]));
+        let walker = test_world.spawn_piece(IVec2::new(1, 1), Faction::Player, 10);
+
+        test_world.enqueue(walker, walk(walker, IVec2::new(1, 1), IVec2::new(2, 1)));
+        test_world.run();
+
+        test_world.assert_position(walker, IVec2::new(2, 1));
+        test_world.assert_executed::<WalkAction>(1);
+    }
+
+    #[test]
+    fn cannot_walk_into_a_wall() {
+        let mut test_world = TestWorld::new(TestWorld::map(5, 5, &[IVec2::new(2, 1)]));
+        let walker = test_world.spawn_piece(IVec2::new(1, 1), Faction::Player, 10);
+
+        test_world.enqueue(walker, walk(walker, IVec2::new(1, 1), IVec2::new(2, 1)));
+        test_world.run();
+
+        test_world.assert_position(walker, IVec2::new(1, 1));
+        test_world.assert_executed::<WalkAction>(0);
+        test_world.assert_rejected::<WalkAction>(ActionError::BlockedByTerrain);
+    }
+
+    #[test]
+    fn cannot_walk_onto_an_occupier() {
+        let mut test_world = TestWorld::new(TestWorld::map(5, 5, &[]));
+        let walker = test_world.spawn_piece(IVec2::new(1, 1), Faction::Player, 10);
+        test_world.spawn_piece(IVec2::new(2, 1), Faction::Foe, 10);
+
+        test_world.enqueue(walker, walk(walker, IVec2::new(1, 1), IVec2::new(2, 1)));
+        test_world.run();
+
+        test_world.assert_position(walker, IVec2::new(1, 1));
+        test_world.assert_executed::<WalkAction>(0);
+        test_world.assert_rejected::<WalkAction>(ActionError::Occupied);
     }
 }
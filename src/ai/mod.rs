@@ -1,24 +1,37 @@
 mod npc;
 
+use std::cmp::Reverse;
+
 use bevy::prelude::*;
-use rand::{thread_rng, Rng};
+use rand::Rng;
 
 use crate::{
-    actions::{skip_action::SkipAction, walk_action::WalkAction, Action, NextActions},
+    actions::{
+        melee_hit_action::{MeleeHitAction, BASIC_ATTACK_DAMAGE},
+        skip_action::SkipAction,
+        walk_action::WalkAction,
+        Action, NextActions,
+    },
     faction::Faction,
-    ivec2::{find_path, ORTHO_DIRECTIONS},
+    ivec2::{find_path, IVec2Ext, ORTHO_DIRECTIONS},
     map::{GameMap, Position},
     pieces::Occupier,
     player::Player,
+    rng::{GameRng, RngStream},
+    stats::Health,
     GamePlayingSet, GameState,
 };
 
-use self::npc::NPCBundle;
+pub(crate) use self::npc::NPCBundle;
 
-const PLAYER_ATTACK_SCORE: i32 = 100;
+const ATTACK_SCORE: i32 = 100;
 const MOVE_SCORE: i32 = 50;
 
-pub struct AIPlugin;
+#[derive(Default)]
+pub struct AIPlugin {
+    /// Perform the planned walks and attacks, the NPCs of the game skip their turns for now
+    pub follow_plans: bool,
+}
 
 impl Plugin for AIPlugin {
     fn build(&self, app: &mut App) {
@@ -29,8 +42,14 @@ impl Plugin for AIPlugin {
                 .in_set(GamePlayingSet::AI),
         )
         .add_systems(Update, plan_walk.in_set(AISet::Planning))
-        .add_systems(Update, npc_action.in_set(AISet::Late))
         .add_systems(OnEnter(GameState::Playing), spawn_npcs);
+
+        if self.follow_plans {
+            app.add_systems(Update, plan_melee.in_set(AISet::Planning))
+                .add_systems(Update, npc_action.in_set(AISet::Late));
+        } else {
+            app.add_systems(Update, npc_skip.in_set(AISet::Late));
+        }
     }
 }
 
@@ -52,7 +71,7 @@ struct PossibleAction {
     action: Box<dyn Action>,
 }
 
-fn spawn_npcs(mut commands: Commands) {
+pub(crate) fn spawn_npcs(mut commands: Commands) {
     spawn_test_npc(&mut commands, IVec2::new(5, 5));
     spawn_test_npc(&mut commands, IVec2::new(4, 5));
 }
@@ -61,21 +80,31 @@ fn spawn_test_npc(commands: &mut Commands, position: IVec2) {
     commands.spawn(NPCBundle::new("NPC".to_string(), position, Faction::Foe));
 }
 
+fn npc_skip(mut query: Query<(Entity, &mut PossibleActions), With<AI>>, mut commands: Commands) {
+    for (entity, mut possible_actions) in query.iter_mut() {
+        possible_actions.0.clear();
+
+        // TODO: replace with possible_actions
+        commands
+            .entity(entity)
+            .insert(NextActions(vec![Box::new(SkipAction { entity })]));
+    }
+}
+
 fn npc_action(mut query: Query<(Entity, &mut PossibleActions), With<AI>>, mut commands: Commands) {
     for (entity, mut possible_actions) in query.iter_mut() {
         let mut possible_actions = possible_actions.0.drain(..).collect::<Vec<_>>();
 
-        possible_actions.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap());
+        possible_actions.sort_by_key(|possible_action| Reverse(possible_action.score));
 
-        let possible_actions = possible_actions
+        let mut next_actions = possible_actions
             .drain(..)
             .map(|possible_action| possible_action.action)
             .collect::<Vec<_>>();
+        // When none of them can be performed
+        next_actions.push(Box::new(SkipAction { entity }));
 
-        // TODO: replace with possible_actions
-        commands
-            .entity(entity)
-            .insert(NextActions(vec![Box::new(SkipAction)]));
+        commands.entity(entity).insert(NextActions(next_actions));
     }
 }
 
@@ -84,7 +113,9 @@ fn plan_walk(
     player_query: Query<&Position, With<Player>>,
     occupier_query: Query<&Position, With<Occupier>>,
     map: Res<GameMap>,
+    mut rng: ResMut<GameRng>,
 ) {
+    let rng = rng.stream(RngStream::AI);
     let Ok(player_position) = player_query.get_single() else {
         return;
     };
@@ -103,12 +134,11 @@ fn plan_walk(
             &map.tiles.keys().cloned().collect(),
             &occupier_query.iter().map(|p| p.0).collect(),
         );
-        let mut rng = thread_rng();
         let walk_possible_actions = positions
             .iter()
             .map(|v| {
                 // randomize movement choices
-                let mut d = rng.gen_range(-10..0);
+                let mut d = rng.random_range(-10..0);
                 if let Some(path) = &path_to_player {
                     // however prioritze a movement if it leads to the player
                     if path.contains(v) {
@@ -129,3 +159,28 @@ fn plan_walk(
         possible_actions.0.extend(walk_possible_actions);
     }
 }
+
+/// Attack the adjacent pieces of the opposing side
+fn plan_melee(
+    mut query: Query<(Entity, &Position, &Faction, &mut PossibleActions), With<AI>>,
+    target_query: Query<(&Position, &Faction), With<Health>>,
+) {
+    for (entity, position, faction, mut possible_actions) in query.iter_mut() {
+        let attack_possible_actions = target_query
+            .iter()
+            .filter(|(target_position, target_faction)| {
+                position.0.manhattan(target_position.0) == 1 && faction.is_enemy(target_faction)
+            })
+            .map(|(target_position, _)| PossibleAction {
+                action: Box::new(MeleeHitAction {
+                    attacker: entity,
+                    target: target_position.0,
+                    damage: BASIC_ATTACK_DAMAGE,
+                }) as Box<dyn Action>,
+                score: ATTACK_SCORE,
+            })
+            .collect::<Vec<_>>();
+
+        possible_actions.0.extend(attack_possible_actions);
+    }
+}
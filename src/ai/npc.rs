@@ -6,6 +6,7 @@ use crate::{
     map::Position,
     pieces::{Actor, FacingOrientation, Occupier, Piece, PieceKind},
     pokemons::Pokemon,
+    stats::Level,
 };
 
 use super::{PossibleActions, AI};
@@ -15,6 +16,7 @@ pub struct NPCBundle {
     actor: Actor,
     name: Name,
     pokemon: Pokemon,
+    level: Level,
     ai: AI,
     possible_actions: PossibleActions,
     occupier: Occupier,
@@ -33,6 +35,7 @@ impl Default for NPCBundle {
                 id: 1,
                 form_index: 0,
             },
+            level: Level::default(),
             ai: AI,
             possible_actions: PossibleActions::default(),
             occupier: Occupier,
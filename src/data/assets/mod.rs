@@ -1,7 +1,9 @@
 pub mod pokemon_data;
+pub mod spell_data;
 pub mod text_data;
 
 use self::pokemon_data::PokemonDataPlugin;
+use self::spell_data::SpellDataPlugin;
 use self::text_data::TextDataPlugin;
 use bevy::prelude::*;
 
@@ -9,6 +11,6 @@ pub struct DataAssetsPlugin;
 
 impl Plugin for DataAssetsPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((PokemonDataPlugin, TextDataPlugin));
+        app.add_plugins((PokemonDataPlugin, SpellDataPlugin, TextDataPlugin));
     }
 }
@@ -0,0 +1,249 @@
+use bevy::prelude::*;
+use pokemon_data::{PokemonData, PromotionDetail};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    actions::{evolve_action::EvolveAction, ActionQueue, QueuedAction},
+    data::assets::pokemon_data::{PokemonConversion, PokemonDataLookup},
+    faction::Faction,
+    pokemons::Pokemon,
+    stats::Level,
+    GamePlayingSet,
+};
+
+pub struct EvolutionPlugin;
+
+impl Plugin for EvolutionPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<KillCount>()
+            .add_systems(Update, check_evolutions.in_set(GamePlayingSet::LateLogics));
+    }
+}
+
+/// Number of foes defeated by the piece, used by the `KillCount` promotions
+#[derive(
+    Component, Debug, Default, Clone, Copy, Reflect, Deref, DerefMut, Serialize, Deserialize,
+)]
+pub struct KillCount(pub u32);
+
+/// The game state a promotion is evaluated against
+struct EvolutionContext {
+    level: u32,
+    form_index: usize,
+    kill_count: u32,
+    allies: u32,
+}
+
+impl EvolutionContext {
+    /// The form the pokemon evolves into if all the `details` are met
+    fn evaluate(&self, details: &[PromotionDetail]) -> Option<usize> {
+        let mut form_index = 0;
+        for detail in details {
+            match detail {
+                PromotionDetail::SetForm { conditions, form } => {
+                    if !conditions.iter().all(|condition| self.meets(condition)) {
+                        return None;
+                    }
+                    form_index = *form as usize;
+                }
+                detail if !self.meets(detail) => return None,
+                _ => {}
+            }
+        }
+        Some(form_index)
+    }
+
+    fn meets(&self, detail: &PromotionDetail) -> bool {
+        match detail {
+            PromotionDetail::Level { level } => self.level >= *level,
+            PromotionDetail::Form { req_form } => self.form_index == *req_form as usize,
+            PromotionDetail::KillCount { amount } => self.kill_count >= *amount,
+            PromotionDetail::Friendship { allies } => self.allies >= *allies,
+            PromotionDetail::SetForm { conditions, .. } => {
+                conditions.iter().all(|condition| self.meets(condition))
+            }
+            // TODO: Items, weather, locations, moves... are not part of the game state yet
+            _ => false,
+        }
+    }
+}
+
+/// Queue an evolution for the pokemons meeting one of their promotions on level-up, the pieces
+/// level up with the experience of the foes they defeat
+#[allow(clippy::type_complexity)]
+fn check_evolutions(
+    query: Query<(
+        Entity,
+        Ref<Level>,
+        &Pokemon,
+        &Handle<PokemonData>,
+        &Faction,
+        Option<&KillCount>,
+    )>,
+    query_faction: Query<&Faction>,
+    pokemon_data: Res<Assets<PokemonData>>,
+    pokemon_data_lookup: Res<PokemonDataLookup>,
+    pokemon_conversion: Res<PokemonConversion>,
+    mut action_queue: ResMut<ActionQueue>,
+) {
+    for (entity, level, pokemon, pokemon_data_handle, faction, kill_count) in query.iter() {
+        if !level.is_changed() || level.is_added() {
+            continue;
+        }
+
+        let Some(data) = pokemon_data.get(pokemon_data_handle) else {
+            warn!("Unable to retrieve pokemon data for evolution");
+            continue;
+        };
+
+        let allies = if faction.is_team() {
+            query_faction.iter().filter(|f| f.is_team()).count() as u32 - 1
+        } else {
+            0
+        };
+
+        let context = EvolutionContext {
+            level: level.0,
+            form_index: pokemon.form_index,
+            kill_count: kill_count.map(|k| k.0).unwrap_or(0),
+            allies,
+        };
+
+        let evolution = data.promotions.iter().find_map(|promotion| {
+            let form_index = context.evaluate(&promotion.details)?;
+            let Some(id) = pokemon_conversion.0.get_by_right(&promotion.result) else {
+                warn!("Failed to find pokemon ID for {}", promotion.result);
+                return None;
+            };
+            if !pokemon_data_lookup.0.contains_key(id) {
+                return None;
+            }
+            Some((*id, form_index))
+        });
+
+        let Some((id, form_index)) = evolution else {
+            continue;
+        };
+
+        info!("{:?} evolves into pokemon ID: {}", entity, id);
+        action_queue.0.push_back(QueuedAction {
+            entity,
+            performable_actions: vec![Box::new(EvolveAction {
+                entity,
+                id,
+                form_index,
+            })],
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use pokemon_data::{PokemonData, Promotion, PromotionDetail};
+
+    use crate::{
+        actions::{evolve_action::EvolveAction, melee_hit_action::MeleeHitAction},
+        data::assets::pokemon_data::{PokemonConversion, PokemonDataLookup},
+        faction::Faction,
+        pokemons::Pokemon,
+        stats::{Experience, Health, Level},
+        test_harness::TestWorld,
+    };
+
+    use super::check_evolutions;
+
+    const BULBASAUR: u32 = 1;
+    const IVYSAUR: u32 = 2;
+    const RATTATA: u32 = 19;
+
+    fn evolution_world() -> (TestWorld, Entity, Entity) {
+        let mut test_world = TestWorld::new(TestWorld::map(5, 5, &[]));
+        let player = test_world.spawn_piece(IVec2::new(1, 2), Faction::Player, 10);
+        let foe = test_world.spawn_piece(IVec2::new(2, 2), Faction::Foe, 1);
+
+        let bulbasaur = TestWorld::pokemon_data(
+            "bulbasaur",
+            64,
+            0,
+            vec![Promotion {
+                result: "ivysaur".to_string(),
+                details: vec![PromotionDetail::Level { level: 6 }],
+            }],
+        );
+        test_world.set_pokemon(player, BULBASAUR, bulbasaur);
+        test_world.set_pokemon(
+            foe,
+            RATTATA,
+            TestWorld::pokemon_data("rattata", 200, 0, vec![]),
+        );
+
+        let ivysaur = test_world
+            .world
+            .resource_mut::<Assets<PokemonData>>()
+            .add(TestWorld::pokemon_data("ivysaur", 142, 0, vec![]));
+        let mut lookup = PokemonDataLookup::default();
+        lookup.0.insert(IVYSAUR, ivysaur);
+        test_world.world.insert_resource(lookup);
+
+        let mut conversion = PokemonConversion::default();
+        conversion.0.insert(BULBASAUR, "bulbasaur".to_string());
+        conversion.0.insert(IVYSAUR, "ivysaur".to_string());
+        conversion.0.insert(RATTATA, "rattata".to_string());
+        test_world.world.insert_resource(conversion);
+
+        (test_world, player, foe)
+    }
+
+    #[test]
+    fn defeating_a_foe_levels_up_and_evolves() {
+        let (mut test_world, player, foe) = evolution_world();
+        let mut check_evolutions = IntoSystem::into_system(check_evolutions);
+        check_evolutions.initialize(&mut test_world.world);
+        // The starting level doesn't trigger an evolution
+        check_evolutions.run((), &mut test_world.world);
+        test_world.run();
+        test_world.assert_executed::<EvolveAction>(0);
+
+        test_world.enqueue(
+            player,
+            vec![Box::new(MeleeHitAction {
+                attacker: player,
+                target: IVec2::new(2, 2),
+                damage: 1,
+            })],
+        );
+        test_world.run();
+        test_world.assert_health(foe, 0);
+        // 200 * 5 / 7 experience on top of the 125 of level 5 reaches level 6 (216)
+        assert_eq!(test_world.world.get::<Experience>(player).unwrap().0, 267);
+        assert_eq!(*test_world.world.get::<Level>(player).unwrap(), Level(6));
+
+        check_evolutions.run((), &mut test_world.world);
+        test_world.run();
+        test_world.assert_executed::<EvolveAction>(1);
+        assert_eq!(test_world.world.get::<Pokemon>(player).unwrap().id, IVYSAUR);
+    }
+
+    #[test]
+    fn foes_do_not_gain_experience() {
+        let (mut test_world, player, foe) = evolution_world();
+        test_world.world.get_mut::<Health>(player).unwrap().value = 1;
+
+        test_world.enqueue(
+            foe,
+            vec![Box::new(MeleeHitAction {
+                attacker: foe,
+                target: IVec2::new(1, 2),
+                damage: 1,
+            })],
+        );
+        test_world.run();
+        test_world.assert_health(player, 0);
+        assert!(test_world.world.get::<Experience>(foe).is_none());
+        assert_eq!(
+            *test_world.world.get::<Level>(foe).unwrap(),
+            Level::default()
+        );
+    }
+}
@@ -1,9 +1,33 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Component)]
+#[derive(Debug, Component, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Faction {
     None,
     Player,
     Friend,
     Foe,
 }
+
+impl Faction {
+    /// The leader and its team members
+    pub fn is_team(&self) -> bool {
+        matches!(self, Faction::Player | Faction::Friend)
+    }
+
+    /// Whether both pieces fight on the same side, a piece without faction has no ally
+    pub fn is_ally(&self, other: &Faction) -> bool {
+        match (self, other) {
+            (Faction::None, _) | (_, Faction::None) => false,
+            _ => self.is_team() == other.is_team(),
+        }
+    }
+
+    /// Whether both pieces fight on opposing sides, a piece without faction is nobody's enemy
+    pub fn is_enemy(&self, other: &Faction) -> bool {
+        match (self, other) {
+            (Faction::None, _) | (_, Faction::None) => false,
+            _ => self.is_team() != other.is_team(),
+        }
+    }
+}
@@ -2,9 +2,7 @@ use bevy::prelude::*;
 use char_animation::anim_key::AnimKey;
 
 use crate::{
-    actions::{
-        destroy_wall_action::DestroyWallAction, melee_hit_action::MeleeHitAction, RunningAction,
-    },
+    actions::{ActionEvent, RunningAction},
     graphics::{animations::Animator, pokemons::PokemonAnimationState},
 };
 
@@ -34,12 +32,9 @@ fn init_attack_animation(
     mut commands: Commands,
 ) {
     for (entity, running_action) in query.iter() {
-        let action = running_action.0.as_any();
-        if action.downcast_ref::<MeleeHitAction>().is_none()
-            && action.downcast_ref::<DestroyWallAction>().is_none()
-        {
+        let ActionEvent::MeleeHit(_) = running_action.0 else {
             continue;
-        }
+        };
 
         ev_animation_playing.send(ActionAnimationPlayingEvent);
 
@@ -1,6 +1,6 @@
 use bevy::{prelude::*, render::view::visibility};
 
-use crate::actions::{death_action::DeathAction, RunningAction};
+use crate::actions::{ActionEvent, RunningAction};
 
 use super::{
     ActionAnimation, ActionAnimationFinishedEvent, ActionAnimationNextEvent,
@@ -38,8 +38,7 @@ fn init_death_animation(
     mut commands: Commands,
 ) {
     for (_entity, running_action) in query.iter() {
-        let action = running_action.0.as_any();
-        let Some(death_action) = action.downcast_ref::<DeathAction>() else {
+        let ActionEvent::Death(death_action) = &running_action.0 else {
             continue;
         };
 
@@ -0,0 +1,131 @@
+use bevy::prelude::*;
+use char_animation::{anim_key::AnimKey, CharAnimation};
+
+use crate::{
+    actions::{ActionEvent, RunningAction},
+    graphics::{assets::pokemon_chara_assets::PokemonCharaAssets, pokemons::PokemonAnimationState},
+    pokemons::Pokemon,
+};
+
+use super::{
+    ActionAnimation, ActionAnimationFinishedEvent, ActionAnimationNextEvent,
+    ActionAnimationPlayingEvent, ActionAnimationSet, AnimationHolder,
+};
+
+const FLASH_NUMBER: u8 = 40;
+const FLASH_DURATION_SECONDS: f32 = 0.04;
+
+pub struct EvolutionAnimationPlugin;
+
+impl Plugin for EvolutionAnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (init_evolution_animation).in_set(ActionAnimationSet::Prepare),
+        )
+        .add_systems(
+            Update,
+            (evolution_animation).in_set(ActionAnimationSet::PlayAnimations),
+        );
+    }
+}
+
+#[derive(Clone)]
+pub struct EvolutionAnimation {
+    pub flash_timer: Timer,
+    pub flash_count: u8,
+}
+
+fn init_evolution_animation(
+    query: Query<(Entity, &RunningAction), Added<RunningAction>>,
+    mut ev_animation_playing: EventWriter<ActionAnimationPlayingEvent>,
+    mut commands: Commands,
+) {
+    for (entity, running_action) in query.iter() {
+        let ActionEvent::Evolve(_) = running_action.0 else {
+            continue;
+        };
+
+        ev_animation_playing.send(ActionAnimationPlayingEvent);
+
+        commands
+            .entity(entity)
+            .insert(AnimationHolder(ActionAnimation::Evolution(
+                EvolutionAnimation {
+                    flash_timer: Timer::from_seconds(FLASH_DURATION_SECONDS, TimerMode::Once),
+                    flash_count: 0,
+                },
+            )));
+    }
+}
+
+/// The pokemon flashes, takes its new appearance halfway through, then keeps flashing
+#[allow(clippy::type_complexity)]
+fn evolution_animation(
+    time: Res<Time>,
+    mut query: Query<(
+        Entity,
+        &Pokemon,
+        &mut AnimationHolder,
+        &mut Visibility,
+        &mut Handle<CharAnimation>,
+        &mut PokemonAnimationState,
+    )>,
+    pokemon_char_assets: Res<PokemonCharaAssets>,
+    mut ev_animation_playing: EventWriter<ActionAnimationPlayingEvent>,
+    mut ev_animation_finished: EventWriter<ActionAnimationFinishedEvent>,
+    mut ev_animation_next: EventWriter<ActionAnimationNextEvent>,
+) {
+    for (
+        entity,
+        pokemon,
+        mut animation,
+        mut visibility,
+        mut char_animation_handle,
+        mut animation_state,
+    ) in query.iter_mut()
+    {
+        let AnimationHolder(ActionAnimation::Evolution(evolution_animation)) = animation.as_mut()
+        else {
+            continue;
+        };
+
+        evolution_animation.flash_timer.tick(time.delta());
+
+        if !evolution_animation.flash_timer.finished() {
+            ev_animation_playing.send(ActionAnimationPlayingEvent);
+            continue;
+        }
+
+        if evolution_animation.flash_count >= FLASH_NUMBER {
+            *visibility = Visibility::Inherited;
+            ev_animation_finished.send(ActionAnimationFinishedEvent(entity));
+            ev_animation_next.send(ActionAnimationNextEvent(entity));
+            continue;
+        }
+
+        evolution_animation.flash_count += 1;
+        evolution_animation.flash_timer =
+            Timer::from_seconds(FLASH_DURATION_SECONDS, TimerMode::Once);
+
+        if evolution_animation.flash_count == FLASH_NUMBER / 2 {
+            match pokemon_char_assets.0.get(&pokemon.id) {
+                Some(handle) => {
+                    *char_animation_handle = handle.clone();
+                    // Force the animator to be rebuilt with the new appearance
+                    animation_state.0 = AnimKey::Idle;
+                    animation_state.set_changed();
+                }
+                None => warn!("Failed to find chara animation for ID: {}", pokemon.id),
+            }
+        }
+
+        *visibility = if evolution_animation.flash_count % 2 == 0 {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+
+        ev_animation_playing.send(ActionAnimationPlayingEvent);
+    }
+}
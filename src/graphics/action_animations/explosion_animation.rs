@@ -0,0 +1,109 @@
+use bevy::prelude::*;
+
+use crate::{
+    actions::{ActionEvent, RunningAction},
+    graphics::{
+        animations::Animator, get_world_position, visual_effects::AutoDespawnEffect, EFFECT_Z,
+    },
+    map::{GameMap, TerrainType},
+    visual_effects::VisualEffect,
+};
+
+use super::{
+    ActionAnimation, ActionAnimationFinishedEvent, ActionAnimationNextEvent,
+    ActionAnimationPlayingEvent, ActionAnimationSet, AnimationHolder,
+};
+
+pub struct ExplosionAnimationPlugin;
+
+impl Plugin for ExplosionAnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (init_explosion_animation).in_set(ActionAnimationSet::Prepare),
+        )
+        .add_systems(
+            Update,
+            (explosion_animation).in_set(ActionAnimationSet::PlayAnimations),
+        );
+    }
+}
+
+#[derive(Clone)]
+pub struct ExplosionAnimation {
+    pub caster: Entity,
+}
+
+fn init_explosion_animation(
+    query: Query<&RunningAction, Added<RunningAction>>,
+    map: Res<GameMap>,
+    mut ev_animation_playing: EventWriter<ActionAnimationPlayingEvent>,
+    mut commands: Commands,
+) {
+    for running_action in query.iter() {
+        let ActionEvent::SpellExplosion(explosion_action) = &running_action.0 else {
+            continue;
+        };
+
+        ev_animation_playing.send(ActionAnimationPlayingEvent);
+
+        let tiles = map
+            .tiles
+            .iter()
+            .filter(|(position, tile)| {
+                tile.r#type != TerrainType::Wall && explosion_action.is_in_range(**position)
+            })
+            .map(|(position, _)| *position)
+            .filter(|position| *position != explosion_action.center)
+            .chain([explosion_action.center]);
+
+        for position in tiles {
+            let visual_effect = &explosion_action.explosion.visual_effect;
+            let mut effect = commands.spawn((
+                Name::new(visual_effect.clone()),
+                VisualEffect {
+                    name: visual_effect.clone(),
+                    is_loop: false,
+                },
+                SpatialBundle {
+                    transform: Transform::from_translation(get_world_position(&position, EFFECT_Z)),
+                    ..default()
+                },
+            ));
+
+            // The effect of the impact tile tells when the explosion is over
+            if position == explosion_action.center {
+                effect.insert(AnimationHolder(ActionAnimation::Explosion(
+                    ExplosionAnimation {
+                        caster: explosion_action.caster,
+                    },
+                )));
+            } else {
+                effect.insert(AutoDespawnEffect);
+            }
+        }
+    }
+}
+
+fn explosion_animation(
+    mut query: Query<(Entity, &mut AnimationHolder, &Animator), With<VisualEffect>>,
+    mut ev_animation_playing: EventWriter<ActionAnimationPlayingEvent>,
+    mut ev_animation_finished: EventWriter<ActionAnimationFinishedEvent>,
+    mut ev_animation_next: EventWriter<ActionAnimationNextEvent>,
+    mut commands: Commands,
+) {
+    for (entity, mut animation, animator) in query.iter_mut() {
+        let AnimationHolder(ActionAnimation::Explosion(animation)) = animation.as_mut() else {
+            continue;
+        };
+
+        if animator.is_finished() {
+            ev_animation_finished.send(ActionAnimationFinishedEvent(animation.caster));
+            ev_animation_next.send(ActionAnimationNextEvent(animation.caster));
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        ev_animation_playing.send(ActionAnimationPlayingEvent);
+    }
+}
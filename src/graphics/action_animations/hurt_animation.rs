@@ -2,7 +2,7 @@ use bevy::prelude::*;
 use char_animation::{anim_key::AnimKey, orientation::Orientation};
 
 use crate::{
-    actions::{damage_action::DamageAction, RunningAction},
+    actions::{ActionEvent, RunningAction},
     graphics::{
         animations::Animator,
         pokemons::{
@@ -50,8 +50,7 @@ fn init_hurt_animation(
     mut commands: Commands,
 ) {
     for (entity, running_action) in query.iter() {
-        let action = running_action.0.as_any();
-        let Some(damage_action) = action.downcast_ref::<DamageAction>() else {
+        let ActionEvent::Damage(damage_action) = &running_action.0 else {
             continue;
         };
 
@@ -90,7 +89,7 @@ fn init_hurt_animation(
                 parent.spawn((
                     Name::new("Hit_Neutral"),
                     VisualEffect {
-                        name: "Hit_Neutral",
+                        name: "Hit_Neutral".to_string(),
                         is_loop: false,
                     },
                     AutoDespawnEffect,
@@ -0,0 +1,92 @@
+use bevy::prelude::*;
+use char_animation::anim_key::AnimKey;
+
+use crate::{
+    actions::{ActionEvent, RunningAction},
+    graphics::pokemons::PokemonAnimationState,
+};
+
+use super::{
+    ActionAnimation, ActionAnimationFinishedEvent, ActionAnimationNextEvent,
+    ActionAnimationPlayingEvent, ActionAnimationSet, AnimationHolder,
+};
+
+const LEAVE_DURATION_SECONDS: f32 = 0.8;
+
+pub struct LeaveAnimationPlugin;
+
+impl Plugin for LeaveAnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (init_leave_animation).in_set(ActionAnimationSet::Prepare),
+        )
+        .add_systems(
+            Update,
+            (leave_animation).in_set(ActionAnimationSet::PlayAnimations),
+        );
+    }
+}
+
+#[derive(Clone)]
+pub struct LeaveAnimation {
+    pub timer: Timer,
+}
+
+fn init_leave_animation(
+    query: Query<(Entity, &RunningAction), Added<RunningAction>>,
+    mut ev_animation_playing: EventWriter<ActionAnimationPlayingEvent>,
+    mut commands: Commands,
+) {
+    for (entity, running_action) in query.iter() {
+        let ActionEvent::Leave(_) = running_action.0 else {
+            continue;
+        };
+
+        ev_animation_playing.send(ActionAnimationPlayingEvent);
+
+        commands.entity(entity).insert((
+            AnimationHolder(ActionAnimation::Leave(LeaveAnimation {
+                timer: Timer::from_seconds(LEAVE_DURATION_SECONDS, TimerMode::Once),
+            })),
+            PokemonAnimationState(AnimKey::Walk),
+        ));
+    }
+}
+
+/// The pokemon walks in place while fading out, it is removed from the floor afterwards
+fn leave_animation(
+    time: Res<Time>,
+    mut query: Query<(
+        Entity,
+        &mut AnimationHolder,
+        Option<&mut Sprite>,
+        &mut Visibility,
+    )>,
+    mut ev_animation_playing: EventWriter<ActionAnimationPlayingEvent>,
+    mut ev_animation_finished: EventWriter<ActionAnimationFinishedEvent>,
+    mut ev_animation_next: EventWriter<ActionAnimationNextEvent>,
+) {
+    for (entity, mut animation, sprite, mut visibility) in query.iter_mut() {
+        let AnimationHolder(ActionAnimation::Leave(leave_animation)) = animation.as_mut() else {
+            continue;
+        };
+
+        leave_animation.timer.tick(time.delta());
+
+        if leave_animation.timer.finished() {
+            *visibility = Visibility::Hidden;
+            ev_animation_finished.send(ActionAnimationFinishedEvent(entity));
+            ev_animation_next.send(ActionAnimationNextEvent(entity));
+            continue;
+        }
+
+        if let Some(mut sprite) = sprite {
+            sprite
+                .color
+                .set_alpha(leave_animation.timer.fraction_remaining());
+        }
+
+        ev_animation_playing.send(ActionAnimationPlayingEvent);
+    }
+}
@@ -1,29 +1,34 @@
-use std::any::TypeId;
-
 use bevy::prelude::*;
 use char_animation::anim_key::AnimKey;
 
 use crate::{
-    actions::{skip_action::SkipAction, RunningAction},
+    actions::{ActionEvent, RunningAction},
     GamePlayingSet,
 };
 
 use self::{
     attack_animation::AttackAnimationPlugin, death_animation::DeathAnimationPlugin,
-    hurt_animation::HurtAnimationPlugin, move_animation::MoveAnimationPlugin,
-    projectile_animation::ProjectileAnimationPlugin,
-    spell_cast_animation::SpellCastAnimationPlugin, spell_hit_animation::SpellHitAnimationPlugin,
+    evolution_animation::EvolutionAnimationPlugin, explosion_animation::ExplosionAnimationPlugin,
+    hurt_animation::HurtAnimationPlugin, leave_animation::LeaveAnimationPlugin,
+    move_animation::MoveAnimationPlugin, projectile_animation::ProjectileAnimationPlugin,
+    recruit_animation::RecruitAnimationPlugin, spell_cast_animation::SpellCastAnimationPlugin,
+    spell_hit_animation::SpellHitAnimationPlugin, trap_animation::TrapAnimationPlugin,
 };
 
 use super::pokemons::PokemonAnimationState;
 
 mod attack_animation;
 mod death_animation;
+mod evolution_animation;
+mod explosion_animation;
 mod hurt_animation;
+mod leave_animation;
 mod move_animation;
 mod projectile_animation;
+mod recruit_animation;
 mod spell_cast_animation;
 mod spell_hit_animation;
+mod trap_animation;
 
 pub struct ActionAnimationPlugin;
 
@@ -40,6 +45,11 @@ impl Plugin for ActionAnimationPlugin {
                 SpellCastAnimationPlugin,
                 SpellHitAnimationPlugin,
                 DeathAnimationPlugin,
+                TrapAnimationPlugin,
+                RecruitAnimationPlugin,
+                LeaveAnimationPlugin,
+                EvolutionAnimationPlugin,
+                ExplosionAnimationPlugin,
             ))
             .configure_sets(
                 Update,
@@ -86,11 +96,16 @@ pub enum ActionAnimation {
     Projectile(projectile_animation::ProjectileAnimation),
     SpellHit(spell_hit_animation::SpellHitAnimation),
     SpellCast(spell_cast_animation::SpellCastAnimation),
+    Explosion(explosion_animation::ExplosionAnimation),
     /* #endregion */
     Move(move_animation::MoveAnimation),
     Attack,
     Hurt(hurt_animation::HurtAnimation),
     Death(death_animation::DeathAnimation),
+    Trap,
+    Recruit,
+    Leave(leave_animation::LeaveAnimation),
+    Evolution(evolution_animation::EvolutionAnimation),
 }
 
 #[derive(Component)]
@@ -122,14 +137,12 @@ fn add_action_animation(
 ) {
     for (entity, running_action) in query.iter() {
         // ev_animation_playing.send(ActionAnimationPlayingEvent);
-        let action = running_action.0.as_any();
         // TODO: move somewhere else
-        match action.type_id() {
-            id if id == TypeId::of::<SkipAction>() => {
-                ev_animation_finished.send(ActionAnimationFinishedEvent(entity));
-                ev_animation_next.send(ActionAnimationNextEvent(entity));
-            }
-            _ => {}
+        if let ActionEvent::Skip(_) | ActionEvent::SpellStrike(_) | ActionEvent::ModifyTerrain(_) =
+            running_action.0
+        {
+            ev_animation_finished.send(ActionAnimationFinishedEvent(entity));
+            ev_animation_next.send(ActionAnimationNextEvent(entity));
         }
     }
 }
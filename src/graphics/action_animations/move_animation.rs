@@ -2,7 +2,7 @@ use bevy::prelude::*;
 use char_animation::anim_key::AnimKey;
 
 use crate::{
-    actions::{walk_action::WalkAction, RunningAction},
+    actions::{ActionBatch, ActionEvent, RunningAction},
     constants::GAME_SPEED,
     graphics::{
         animations::Animator, get_world_position, pokemons::PokemonAnimationState, POKEMON_Z,
@@ -55,8 +55,7 @@ fn init_move_animation(
     mut commands: Commands,
 ) {
     for (entity, running_action) in query.iter_mut() {
-        let action = running_action.0.as_any();
-        let Some(walk_action) = action.downcast_ref::<WalkAction>() else {
+        let ActionEvent::Walk(walk_action) = &running_action.0 else {
             continue;
         };
 
@@ -75,17 +74,28 @@ fn init_move_animation(
 
 fn move_animation(
     mut query: Query<(
+        Entity,
         &mut AnimationHolder,
         &mut PokemonAnimationState,
         &mut Transform,
         &Animator,
     )>,
+    batch: Res<ActionBatch>,
     time: Res<Time>,
     mut ev_animation_playing: EventWriter<ActionAnimationPlayingEvent>,
     mut ev_animation_finished: EventWriter<ActionAnimationFinishedEvent>,
     mut ev_animation_next: EventWriter<ActionAnimationNextEvent>,
 ) {
-    for (mut animation, mut animation_state, mut transform, animator) in query.iter_mut() {
+    // The walks of a batch end together
+    let is_batch_walking = query.iter().any(|(entity, animation, _, transform, _)| {
+        let AnimationHolder(ActionAnimation::Move(move_animation)) = animation else {
+            return false;
+        };
+        batch.entities.contains(&entity)
+            && (move_animation.to - transform.translation).length() > POSITION_TOLERANCE
+    });
+
+    for (entity, mut animation, mut animation_state, mut transform, animator) in query.iter_mut() {
         let AnimationHolder(ActionAnimation::Move(move_animation)) = animation.as_mut() else {
             continue;
         };
@@ -106,6 +116,10 @@ fn move_animation(
         // the entity is at the desired path position
         // transform.translation = move_animation.to;
 
+        if is_batch_walking && batch.entities.contains(&entity) {
+            continue;
+        }
+
         ev_animation_next.send(ActionAnimationNextEvent(move_animation.entity));
 
         if !animator.is_finished() {
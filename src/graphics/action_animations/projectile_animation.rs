@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 
 use crate::{
-    actions::{spell_projectile_action::SpellProjectileAction, RunningAction},
+    actions::{ActionEvent, RunningAction},
     constants::GAME_SPEED,
     graphics::{
         animations::Animator, get_world_position, pokemons::offsets::PokemonHeadOffset, EFFECT_Z,
@@ -36,6 +36,8 @@ pub struct ProjectileAnimation {
     pub caster: Entity,
     pub to: Vec3,
     pub from: Vec3,
+    /// Fly back to `from` once `to` is reached
+    pub boomerang: bool,
     t: f32,
 }
 
@@ -46,8 +48,7 @@ fn init_projectile_animation(
     mut commands: Commands,
 ) {
     for (entity, running_action, position, children) in query.iter() {
-        let action = running_action.0.as_any();
-        let Some(spell_projectile_action) = action.downcast_ref::<SpellProjectileAction>() else {
+        let ActionEvent::SpellProjectile(spell_projectile_action) = &running_action.0 else {
             continue;
         };
 
@@ -65,9 +66,9 @@ fn init_projectile_animation(
         let to = get_world_position(&spell_projectile_action.target, EFFECT_Z);
 
         commands.spawn((
-            Name::new(spell_projectile_action.projectile.visual_effect.to_string()),
+            Name::new(spell_projectile_action.projectile.visual_effect.clone()),
             VisualEffect {
-                name: spell_projectile_action.projectile.visual_effect,
+                name: spell_projectile_action.projectile.visual_effect.clone(),
                 is_loop: true,
             },
             SpatialBundle {
@@ -78,6 +79,7 @@ fn init_projectile_animation(
                 caster: spell_projectile_action.caster,
                 to,
                 from,
+                boomerang: spell_projectile_action.projectile.boomerang,
                 t: 0.,
             })),
         ));
@@ -115,6 +117,13 @@ fn projectile_animation(
         // the entity is at the desired path position
         transform.translation = projectile_animation.to;
 
+        if projectile_animation.boomerang {
+            std::mem::swap(&mut projectile_animation.from, &mut projectile_animation.to);
+            projectile_animation.boomerang = false;
+            projectile_animation.t = 0.;
+            continue;
+        }
+
         commands.entity(entity).despawn_recursive();
 
         ev_animation_finished.send(ActionAnimationFinishedEvent(projectile_animation.caster));
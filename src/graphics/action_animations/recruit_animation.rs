@@ -0,0 +1,67 @@
+use bevy::prelude::*;
+
+use crate::{
+    actions::{ActionEvent, RunningAction},
+    recruitment::RecruitmentPrompt,
+};
+
+use super::{
+    ActionAnimation, ActionAnimationFinishedEvent, ActionAnimationNextEvent,
+    ActionAnimationPlayingEvent, ActionAnimationSet, AnimationHolder,
+};
+
+pub struct RecruitAnimationPlugin;
+
+impl Plugin for RecruitAnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (init_recruit_animation).in_set(ActionAnimationSet::Prepare),
+        )
+        .add_systems(
+            Update,
+            (recruit_animation).in_set(ActionAnimationSet::PlayAnimations),
+        );
+    }
+}
+
+fn init_recruit_animation(
+    query: Query<(Entity, &RunningAction), Added<RunningAction>>,
+    mut ev_animation_playing: EventWriter<ActionAnimationPlayingEvent>,
+    mut commands: Commands,
+) {
+    for (entity, running_action) in query.iter() {
+        let ActionEvent::Recruit(_) = running_action.0 else {
+            continue;
+        };
+
+        ev_animation_playing.send(ActionAnimationPlayingEvent);
+
+        commands
+            .entity(entity)
+            .insert(AnimationHolder(ActionAnimation::Recruit));
+    }
+}
+
+/// Hold the action queue until the player answered the recruitment prompt
+fn recruit_animation(
+    query: Query<(Entity, &AnimationHolder)>,
+    prompt: Res<RecruitmentPrompt>,
+    mut ev_animation_playing: EventWriter<ActionAnimationPlayingEvent>,
+    mut ev_animation_finished: EventWriter<ActionAnimationFinishedEvent>,
+    mut ev_animation_next: EventWriter<ActionAnimationNextEvent>,
+) {
+    for (entity, animation) in query.iter() {
+        let AnimationHolder(ActionAnimation::Recruit) = animation else {
+            continue;
+        };
+
+        if prompt.is_pending() {
+            ev_animation_playing.send(ActionAnimationPlayingEvent);
+            continue;
+        }
+
+        ev_animation_finished.send(ActionAnimationFinishedEvent(entity));
+        ev_animation_next.send(ActionAnimationNextEvent(entity));
+    }
+}
@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 
 use crate::{
-    actions::{damage_action::DamageAction, spell_action::SpellAction, RunningAction},
+    actions::{damage_action::DamageAction, ActionEvent, RunningAction},
     graphics::{
         animations::Animator,
         pokemons::{offsets::PokemonHeadOffset, PokemonAnimationState},
@@ -44,8 +44,7 @@ fn init_spell_cast_animation(
     mut commands: Commands,
 ) {
     for (entity, running_action, children) in query.iter() {
-        let action = running_action.0.as_any();
-        let Some(spell_action) = action.downcast_ref::<SpellAction>() else {
+        let ActionEvent::Spell(spell_action) = &running_action.0 else {
             continue;
         };
 
@@ -70,9 +69,9 @@ fn init_spell_cast_animation(
             .with_children(|parent| {
                 // Visual Effect
                 parent.spawn((
-                    Name::new(spell_action.spell.cast.visual_effect.to_string()),
+                    Name::new(spell_action.spell.cast.visual_effect.clone()),
                     VisualEffect {
-                        name: spell_action.spell.cast.visual_effect,
+                        name: spell_action.spell.cast.visual_effect.clone(),
                         is_loop: false,
                     },
                     AutoDespawnEffect,
@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 
 use crate::{
-    actions::{spell_hit_action::SpellHitAction, RunningAction},
+    actions::{ActionEvent, RunningAction},
     graphics::{animations::Animator, pokemons::offsets::PokemonBodyOffset},
     visual_effects::VisualEffect,
 };
@@ -40,8 +40,7 @@ fn init_spell_hit_animation(
     mut commands: Commands,
 ) {
     for (entity, running_action) in query.iter() {
-        let action = running_action.0.as_any();
-        let Some(spell_hit_action) = action.downcast_ref::<SpellHitAction>() else {
+        let ActionEvent::SpellHit(spell_hit_action) = &running_action.0 else {
             continue;
         };
 
@@ -59,9 +58,9 @@ fn init_spell_hit_animation(
             .entity(target_entity_hist_effect)
             .with_children(|parent| {
                 parent.spawn((
-                    Name::new(spell_hit_action.hit.visual_effect.to_string()),
+                    Name::new(spell_hit_action.hit.visual_effect.clone()),
                     VisualEffect {
-                        name: spell_hit_action.hit.visual_effect,
+                        name: spell_hit_action.hit.visual_effect.clone(),
                         is_loop: false,
                     },
                     SpatialBundle {
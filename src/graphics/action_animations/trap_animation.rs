@@ -0,0 +1,84 @@
+use bevy::prelude::*;
+use char_animation::anim_key::AnimKey;
+
+use crate::{
+    actions::{ActionEvent, RunningAction},
+    graphics::{
+        animations::Animator, get_world_position, pokemons::PokemonAnimationState, POKEMON_Z,
+    },
+    map::Position,
+    traps::TrapKind,
+};
+
+use super::{
+    ActionAnimation, ActionAnimationFinishedEvent, ActionAnimationNextEvent,
+    ActionAnimationPlayingEvent, ActionAnimationSet, AnimationHolder,
+};
+
+pub struct TrapAnimationPlugin;
+
+impl Plugin for TrapAnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (init_trap_animation).in_set(ActionAnimationSet::Prepare),
+        )
+        .add_systems(
+            Update,
+            (trap_animation).in_set(ActionAnimationSet::PlayAnimations),
+        );
+    }
+}
+
+fn init_trap_animation(
+    mut query: Query<(Entity, &RunningAction, &Position, &mut Transform), Added<RunningAction>>,
+    mut ev_animation_playing: EventWriter<ActionAnimationPlayingEvent>,
+    mut ev_animation_finished: EventWriter<ActionAnimationFinishedEvent>,
+    mut ev_animation_next: EventWriter<ActionAnimationNextEvent>,
+    mut commands: Commands,
+) {
+    for (entity, running_action, position, mut transform) in query.iter_mut() {
+        let ActionEvent::TriggerTrap(trigger_trap_action) = &running_action.0 else {
+            continue;
+        };
+
+        match trigger_trap_action.kind {
+            TrapKind::Trip => {
+                ev_animation_playing.send(ActionAnimationPlayingEvent);
+
+                commands.entity(entity).insert((
+                    AnimationHolder(ActionAnimation::Trap),
+                    PokemonAnimationState(AnimKey::Trip),
+                ));
+            }
+            _ => {
+                // The warp trap moves the piece instantly
+                transform.translation = get_world_position(&position.0, POKEMON_Z);
+
+                ev_animation_finished.send(ActionAnimationFinishedEvent(entity));
+                ev_animation_next.send(ActionAnimationNextEvent(entity));
+            }
+        }
+    }
+}
+
+fn trap_animation(
+    mut query: Query<(Entity, &AnimationHolder, &Animator)>,
+    mut ev_animation_playing: EventWriter<ActionAnimationPlayingEvent>,
+    mut ev_animation_finished: EventWriter<ActionAnimationFinishedEvent>,
+    mut ev_animation_next: EventWriter<ActionAnimationNextEvent>,
+) {
+    for (entity, animation, animator) in query.iter_mut() {
+        let AnimationHolder(ActionAnimation::Trap) = animation else {
+            continue;
+        };
+
+        if animator.is_finished() {
+            ev_animation_finished.send(ActionAnimationFinishedEvent(entity));
+            ev_animation_next.send(ActionAnimationNextEvent(entity));
+            continue;
+        }
+
+        ev_animation_playing.send(ActionAnimationPlayingEvent);
+    }
+}
@@ -1,4 +1,4 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, sprite::Anchor};
 use bevy_inspector_egui::InspectorOptions;
 use char_animation::orientation::Orientation;
 use std::time::Duration;
@@ -29,6 +29,7 @@ pub struct AnimationFrameChangedEvent {
 pub struct AnimationFrame {
     pub atlas_index: usize,
     pub duration: Duration,
+    pub anchor: Anchor,
 }
 
 #[derive(Component, Debug, Default, InspectorOptions)]
@@ -116,11 +117,16 @@ impl AnimationIndices {
 
 fn animation_system(
     time: Res<Time>,
-    mut query: Query<(Entity, &mut Animator, &mut TextureAtlas)>,
+    mut query: Query<(
+        Entity,
+        &mut Animator,
+        &mut TextureAtlas,
+        Option<&mut Sprite>,
+    )>,
     mut ev_finished: EventWriter<AnimationFinished>,
     mut ev_animation_frame_changed: EventWriter<AnimationFrameChangedEvent>,
 ) {
-    for (entity, mut animator, mut sprite) in &mut query.iter_mut() {
+    for (entity, mut animator, mut sprite, sprite_settings) in &mut query.iter_mut() {
         animator.timer.tick(time.delta());
 
         if !animator.timer.finished() {
@@ -142,6 +148,11 @@ fn animation_system(
         animator.timer.set_duration(frame.duration);
         animator.timer.reset();
         sprite.index = frame.atlas_index;
+        if let Some(mut sprite_settings) = sprite_settings {
+            if sprite_settings.anchor != frame.anchor {
+                sprite_settings.anchor = frame.anchor;
+            }
+        }
 
         ev_animation_frame_changed.send(AnimationFrameChangedEvent {
             entity,
@@ -9,6 +9,7 @@ use crate::GameState;
 pub mod binary_data;
 pub mod font_assets;
 pub mod pokemon_chara_assets;
+pub mod pokemon_portrait_assets;
 pub mod shadow_assets;
 pub mod ui_assets;
 pub mod visual_effect_assets;
@@ -16,6 +17,7 @@ pub mod visual_effect_assets;
 use self::binary_data::BinaryDataPlugin;
 use self::font_assets::FontAssetsPlugin;
 use self::pokemon_chara_assets::PokemonCharaAssetsPlugin;
+use self::pokemon_portrait_assets::PokemonPortraitAssetsPlugin;
 use self::shadow_assets::ShadowAssetsPlugin;
 use self::ui_assets::UIAssetsPlugin;
 use self::visual_effect_assets::VisualEffectAssetsPlugin;
@@ -30,6 +32,7 @@ impl Plugin for GraphicAssetsPlugin {
             BinaryDataPlugin,
             UIAssetsPlugin,
             PokemonCharaAssetsPlugin,
+            PokemonPortraitAssetsPlugin,
             ShadowAssetsPlugin,
         ))
         .init_collection::<TileAssets>();
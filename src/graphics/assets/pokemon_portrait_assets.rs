@@ -0,0 +1,90 @@
+use std::str::FromStr;
+
+use crate::loading::AssetsLoading;
+use crate::GameState;
+#[cfg(not(target_arch = "wasm32"))]
+use bevy::asset::io::file::FileAssetReader;
+use bevy::asset::LoadedFolder;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use portrait::Portrait;
+
+const PORTRAIT_FOLDER: &str = "portraits";
+
+pub struct PokemonPortraitAssetsPlugin;
+
+impl Plugin for PokemonPortraitAssetsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(PortraitAssetsFolder(default()))
+            .init_resource::<PokemonPortraitAssets>()
+            .add_systems(OnEnter(GameState::Loading), load_assets_folder)
+            .add_systems(OnEnter(GameState::AssetsLoaded), process_portrait_assets);
+    }
+}
+
+/// The emotion faces of the pokemons by pokemon ID, the species without portraits are missing
+#[derive(Resource, Debug, Default)]
+pub struct PokemonPortraitAssets(pub HashMap<u32, Handle<Portrait>>);
+
+#[derive(Default, Resource)]
+struct PortraitAssetsFolder(Handle<LoadedFolder>);
+
+fn load_assets_folder(
+    asset_server: Res<AssetServer>,
+    mut loading: ResMut<AssetsLoading>,
+    mut portrait_assets_folder: ResMut<PortraitAssetsFolder>,
+) {
+    // The portraits are optional, an unbuilt folder would only log a loading error
+    if !portrait_folder_exists() {
+        return;
+    }
+
+    info!("portrait assets loading...");
+    portrait_assets_folder.0 = asset_server.load_folder(PORTRAIT_FOLDER);
+    loading.0.push(portrait_assets_folder.0.clone().untyped());
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn portrait_folder_exists() -> bool {
+    FileAssetReader::get_base_path()
+        .join("assets")
+        .join(PORTRAIT_FOLDER)
+        .is_dir()
+}
+
+/// The web assets can't be listed, the folder is loaded and may be missing
+#[cfg(target_arch = "wasm32")]
+fn portrait_folder_exists() -> bool {
+    true
+}
+
+fn process_portrait_assets(
+    portrait_assets_folder: Res<PortraitAssetsFolder>,
+    loaded_folder_assets: Res<Assets<LoadedFolder>>,
+    mut pokemon_portrait_assets: ResMut<PokemonPortraitAssets>,
+    mut commands: Commands,
+) {
+    // Clean up unused resources
+    commands.remove_resource::<PortraitAssetsFolder>();
+
+    // The portraits are optional, the game runs without them
+    let Some::<&LoadedFolder>(portrait_folder) =
+        loaded_folder_assets.get(&portrait_assets_folder.0)
+    else {
+        warn!("No portraits loaded, build them in the assets/portraits directory");
+        return;
+    };
+
+    pokemon_portrait_assets.0 = portrait_folder
+        .handles
+        .iter()
+        .filter_map(|handle| {
+            let file_stem = handle.path()?.path().file_stem()?.to_str()?;
+            let Ok(pokemon_id) = u32::from_str(file_stem) else {
+                warn!("Skipping the portrait {file_stem}, it isn't named after a pokemon id");
+                return None;
+            };
+            Some((pokemon_id, handle.to_owned().typed::<Portrait>()))
+        })
+        .collect::<HashMap<_, _>>();
+}
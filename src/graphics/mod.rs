@@ -2,8 +2,8 @@ use bevy::prelude::*;
 
 use self::{
     action_animations::ActionAnimationPlugin, animations::AnimationsPlugin,
-    assets::GraphicAssetsPlugin, pokemons::PokemonPlugin, tiles::TilesPlugin, ui::UIPlugin,
-    visual_effects::VisualEffectsPlugin, world_number::WorldNumberPlugin,
+    assets::GraphicAssetsPlugin, pokemons::PokemonPlugin, tiles::TilesPlugin, traps::TrapsPlugin,
+    ui::UIPlugin, visual_effects::VisualEffectsPlugin, world_number::WorldNumberPlugin,
 };
 
 pub mod action_animations;
@@ -12,6 +12,7 @@ pub mod assets;
 pub mod pokemons;
 pub mod tile_sprite_index;
 mod tiles;
+mod traps;
 pub mod ui;
 mod visual_effects;
 pub mod world_number;
@@ -36,6 +37,7 @@ impl Plugin for GraphicsPlugin {
         app.add_event::<GraphicsWaitEvent>().add_plugins((
             ActionAnimationPlugin,
             TilesPlugin,
+            TrapsPlugin,
             PokemonPlugin,
             GraphicAssetsPlugin,
             AnimationsPlugin,
@@ -15,7 +15,7 @@ use self::{
         PokemonHeadOffset,
     },
     pokemon_animator::get_pokemon_animator,
-    shadow::{spawn_shadow_renderer, update_shadow_offsets, PokemonShadow},
+    shadow::{spawn_shadow_renderer, update_shadow_faction, update_shadow_offsets, PokemonShadow},
 };
 
 use super::{
@@ -32,12 +32,17 @@ impl Plugin for PokemonPlugin {
             .add_event::<AnimatorUpdatedEvent>()
             .add_systems(
                 Update,
-                (spawn_pokemon_renderer, spawn_shadow_renderer)
+                (
+                    spawn_pokemon_renderer,
+                    spawn_shadow_renderer,
+                    update_shadow_faction,
+                )
                     .run_if(in_state(GameState::Playing)),
             )
             .add_systems(
                 Update,
                 (
+                    refresh_reloaded_animations,
                     update_animator,
                     // update_shadow_animator,
                     // update_offsets_animator,
@@ -65,6 +70,24 @@ pub struct AnimatorUpdatedEvent(pub Entity);
 #[derive(Component, Default, Reflect)]
 pub struct PokemonAnimationState(pub AnimKey);
 
+/// Reapply the animation of the pokemons whose char animation was reloaded
+fn refresh_reloaded_animations(
+    mut ev_char_animation: EventReader<AssetEvent<CharAnimation>>,
+    mut query: Query<(&Handle<CharAnimation>, &mut PokemonAnimationState)>,
+) {
+    for event in ev_char_animation.read() {
+        let AssetEvent::Modified { id } = event else {
+            continue;
+        };
+
+        for (handle, mut animation_state) in query.iter_mut() {
+            if handle.id() == *id {
+                animation_state.set_changed();
+            }
+        }
+    }
+}
+
 #[allow(clippy::type_complexity)]
 fn update_animator(
     mut query: Query<
@@ -40,6 +40,11 @@ pub fn get_pokemon_animator(
                 ((duration * FRAME_DURATION_MILLIS) as f32 / GAME_SPEED).floor() as u64,
             ),
             atlas_index: animation_indices.first + index,
+            anchor: animation_data
+                .frame_anchors
+                .get(animation_indices.first + index)
+                .copied()
+                .unwrap_or_default(),
         })
         .collect::<Vec<_>>();
 
@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use bevy::prelude::*;
+use bevy::{prelude::*, sprite::Anchor};
 use char_animation::CharAnimation;
 
 use crate::{
@@ -46,6 +46,7 @@ impl PokemonShadow {
                 duration: Duration::from_millis(
                     ((10 * FRAME_DURATION_MILLIS) as f32 / GAME_SPEED).floor() as u64,
                 ),
+                anchor: Anchor::Center,
             })
             .collect::<Vec<_>>()
     }
@@ -137,3 +138,23 @@ pub fn update_shadow_offsets(
         }
     }
 }
+
+/// Update the shadow color when the faction changes (e.g. when a foe is recruited)
+pub fn update_shadow_faction(
+    map: Res<GameMap>,
+    query_parent: Query<(&Position, &Faction, &Children), Changed<Faction>>,
+    mut query_shadow: Query<(&PokemonShadow, &mut Animator)>,
+) {
+    for (position, faction, children) in query_parent.iter() {
+        let Some(tile) = map.tiles.get(&position.0) else {
+            continue;
+        };
+
+        for child in children {
+            let Ok((shadow, mut animator)) = query_shadow.get_mut(*child) else {
+                continue;
+            };
+            animator.frames = shadow.get_animation_frames(faction, tile);
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use bevy::prelude::*;
+
+use crate::{
+    map::Position,
+    traps::{HiddenTrap, Trap, TrapKind},
+    GameState,
+};
+
+use super::{TILE_SIZE, TILE_Z};
+
+const TRAP_Z: f32 = TILE_Z + 1.;
+
+pub struct TrapsPlugin;
+
+impl Plugin for TrapsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            spawn_trap_renderer.run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+// TODO: replace the colored squares with the trap sprites
+fn trap_color(kind: &TrapKind) -> Color {
+    match kind {
+        TrapKind::Warp => Color::srgb(0.4, 0.8, 1.0),
+        TrapKind::Spike => Color::srgb(0.6, 0.6, 0.6),
+        TrapKind::Poison => Color::srgb(0.6, 0.2, 0.8),
+        TrapKind::Sticky => Color::srgb(0.2, 0.8, 0.2),
+        TrapKind::Summon => Color::srgb(1.0, 0.3, 0.3),
+        TrapKind::Trip => Color::srgb(1.0, 0.8, 0.2),
+    }
+}
+
+/// Render the traps once they are revealed
+#[allow(clippy::type_complexity)]
+fn spawn_trap_renderer(
+    query: Query<(Entity, &Trap, &Position), (Without<HiddenTrap>, Without<Sprite>)>,
+    mut commands: Commands,
+) {
+    for (entity, trap, position) in query.iter() {
+        commands.entity(entity).insert(SpriteBundle {
+            sprite: Sprite {
+                color: trap_color(&trap.kind),
+                custom_size: Some(Vec2::splat(TILE_SIZE / 2.)),
+                ..default()
+            },
+            transform: Transform::from_translation(super::get_world_position(&position.0, TRAP_Z)),
+            ..default()
+        });
+    }
+}
@@ -12,7 +12,9 @@ pub use node::*;
 pub use text::*;
 pub use ui::*;
 
-use self::render::{new_image_from_default, render_texture, SpriteTextRenderSet};
+use self::render::{
+    new_image_from_default, refresh_reloaded_fonts, render_texture, SpriteTextRenderSet,
+};
 
 pub struct SpriteTextPlugin;
 
@@ -32,7 +34,7 @@ impl Plugin for SpriteTextPlugin {
         .add_systems(
             PostUpdate,
             (
-                (new_image_from_default).in_set(SpriteTextRenderSet::Setup),
+                (new_image_from_default, refresh_reloaded_fonts).in_set(SpriteTextRenderSet::Setup),
                 (render_texture).in_set(SpriteTextRenderSet::Draw),
             ),
         );
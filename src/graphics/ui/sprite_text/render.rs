@@ -23,6 +23,28 @@ pub(crate) fn new_image_from_default(
     }
 }
 
+/// Render again the texts using a reloaded font
+pub(crate) fn refresh_reloaded_fonts(
+    mut ev_font: EventReader<AssetEvent<BitmapFont>>,
+    mut query: Query<&mut SpriteText>,
+) {
+    for event in ev_font.read() {
+        let AssetEvent::Modified { id } = event else {
+            continue;
+        };
+
+        for mut sprite_text in query.iter_mut() {
+            if sprite_text
+                .sections
+                .iter()
+                .any(|section| section.style.font.id() == *id)
+            {
+                sprite_text.set_changed();
+            }
+        }
+    }
+}
+
 #[allow(clippy::type_complexity)]
 pub(crate) fn render_texture(
     mut query: Query<
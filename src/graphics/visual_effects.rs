@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use bevy::prelude::*;
+use bevy::{prelude::*, sprite::Anchor};
 
 use crate::{constants::GAME_SPEED, visual_effects::VisualEffect, GameState};
 
@@ -32,7 +32,8 @@ fn spawn_visual_effect_renderer(
     query: Query<(Entity, &VisualEffect, &Transform), Added<VisualEffect>>,
 ) {
     for (entity, effect, _transform) in query.iter() {
-        let Some(effect_texture_info) = visual_effect_assets.0.get(effect.name).cloned() else {
+        let Some(effect_texture_info) = visual_effect_assets.0.get(effect.name.as_str()).cloned()
+        else {
             warn!("Visual effect texture not found for {}", effect.name);
             continue;
         };
@@ -50,6 +51,7 @@ fn spawn_visual_effect_renderer(
                 duration: Duration::from_millis(
                     ((FRAME_DURATION_MILLIS * 2) as f32 / GAME_SPEED).floor() as u64,
                 ),
+                anchor: Anchor::Center,
             })
             .collect::<Vec<_>>();
 
@@ -5,6 +5,7 @@ use std::{
 
 use bevy::{log::warn, math::IVec2};
 use char_animation::orientation::Orientation;
+use strum::IntoEnumIterator;
 
 pub fn find_path(
     start: IVec2,
@@ -135,6 +136,8 @@ impl IVec2Ext for IVec2 {
 pub trait OrientationExt {
     fn from_vector(direction: IVec2) -> Orientation;
     fn to_vector(&self) -> IVec2;
+    /// Rotate by `steps` eighths of a turn, counterclockwise
+    fn rotate(&self, steps: i32) -> Orientation;
 }
 
 impl OrientationExt for Orientation {
@@ -167,6 +170,12 @@ impl OrientationExt for Orientation {
             Orientation::SouthWest => IVec2 { x: -1, y: -1 },
         }
     }
+
+    fn rotate(&self, steps: i32) -> Orientation {
+        let orientations = Orientation::iter().collect::<Vec<_>>();
+        let index = orientations.iter().position(|o| o == self).unwrap() as i32;
+        orientations[(index + steps).rem_euclid(orientations.len() as i32) as usize].clone()
+    }
 }
 
 pub const ORTHO_DIRECTIONS: [IVec2; 4] = [IVec2::UP, IVec2::DOWN, IVec2::LEFT, IVec2::RIGHT];
@@ -11,12 +11,22 @@ use bitmap_font::BitmapFontPlugin;
 use camera::CameraPlugin;
 use char_animation::CharAnimationPlugin;
 use data::DataPlugin;
+use evolution::EvolutionPlugin;
 use graphics::GraphicsPlugin;
 use loading::LoadingPlugin;
 use pokemon_data::PokemonDataPlugin;
 use pokemons::PokemonsPlugin;
+use portrait::PortraitPlugin;
+use practice::PracticePlugin;
+use recruitment::RecruitmentPlugin;
+use replay::ReplayPlugin;
+use rng::RngPlugin;
+use save::SavePlugin;
+use spell_data::SpellDataPlugin;
 use stats::StatsPlugin;
+use status::StatusPlugin;
 use test::TestPlugin;
+use traps::TrapsPlugin;
 use ui::UIPlugin;
 use visual_effects::VisualEffectsPlugin;
 
@@ -31,6 +41,7 @@ mod ai;
 mod camera;
 mod constants;
 mod data;
+mod evolution;
 mod faction;
 mod graphics;
 mod ivec2;
@@ -41,9 +52,19 @@ mod move_type;
 mod pieces;
 mod player;
 mod pokemons;
+pub mod practice;
+mod recruitment;
+pub mod replay;
+pub mod rng;
+mod save;
+pub mod simulation;
 pub mod spells;
 mod stats;
+mod status;
 mod test;
+#[cfg(test)]
+mod test_harness;
+mod traps;
 mod turn;
 mod ui;
 pub mod utils;
@@ -77,52 +98,67 @@ enum GamePlayingSet {
     LateLogics,
 }
 
+fn configure_game_playing_sets(app: &mut App) {
+    app.configure_sets(
+        Update,
+        (
+            GamePlayingSet::Inputs,
+            GamePlayingSet::Controls,
+            GamePlayingSet::AI.run_if(on_event::<PlayerActionEvent>()),
+            GamePlayingSet::TurnLogics,
+            GamePlayingSet::Animations,
+            GamePlayingSet::Actions,
+            GamePlayingSet::LateLogics,
+        )
+            .chain()
+            .run_if(in_state(GameState::Playing)),
+    );
+}
+
 pub struct GamePlugin;
 
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
-        app.init_state::<GameState>()
-            .configure_sets(
-                Update,
-                (
-                    GamePlayingSet::Inputs,
-                    GamePlayingSet::Controls,
-                    GamePlayingSet::AI.run_if(on_event::<PlayerActionEvent>()),
-                    GamePlayingSet::TurnLogics,
-                    GamePlayingSet::Animations,
-                    GamePlayingSet::Actions,
-                    GamePlayingSet::LateLogics,
-                )
-                    .chain()
-                    .run_if(in_state(GameState::Playing)),
-            )
-            .add_plugins((
-                // LoadingPlugin, // custom assets loading system can't use for now
-                BitmapFontPlugin,
-                CharAnimationPlugin,
-                PokemonDataPlugin,
-                EguiPlugin,
-                MenuPlugin,
-                MapPlugin,
-                PiecesPlugin,
-                GraphicsPlugin,
-                CameraPlugin,
-                PlayerPlugin,
-                AIPlugin,
-                ActionsPlugin,
-                TurnPlugin,
-                VisualEffectsPlugin,
-                //Only for testing purposes
-                TestPlugin,
-            ))
-            .add_plugins((
-                StatsPlugin,
-                DataPlugin,
-                LoadingPlugin,
-                PokemonsPlugin,
-                UIPlugin,
-            ))
-            .add_systems(Update, update_ui_scale.run_if(in_state(GameState::Playing)));
+        app.init_state::<GameState>();
+        configure_game_playing_sets(app);
+
+        app.add_plugins((
+            // LoadingPlugin, // custom assets loading system can't use for now
+            BitmapFontPlugin,
+            CharAnimationPlugin,
+            PokemonDataPlugin,
+            EguiPlugin,
+            MenuPlugin,
+            MapPlugin,
+            PiecesPlugin,
+            GraphicsPlugin,
+            CameraPlugin,
+            PlayerPlugin,
+            AIPlugin::default(),
+            ActionsPlugin,
+            TurnPlugin,
+            VisualEffectsPlugin,
+            //Only for testing purposes
+            TestPlugin,
+        ))
+        .add_plugins((
+            StatsPlugin,
+            DataPlugin,
+            LoadingPlugin,
+            PokemonsPlugin,
+            PortraitPlugin,
+            UIPlugin,
+            StatusPlugin,
+            TrapsPlugin,
+            RecruitmentPlugin,
+            EvolutionPlugin,
+            SavePlugin,
+            RngPlugin,
+            ReplayPlugin,
+            PracticePlugin,
+            SpellDataPlugin,
+        ))
+        .add_systems(Update, update_ui_scale.run_if(in_state(GameState::Playing)));
 
         #[cfg(debug_assertions)]
         {
@@ -1,9 +1,68 @@
+use std::path::PathBuf;
+
 use bevy::{asset::AssetMetaCheck, prelude::*};
-use bevy_pokemon_roguelike::GamePlugin;
+use bevy_pokemon_roguelike::{
+    practice::PracticeMode,
+    replay::{Replay, ReplayPlayback, ReplayRecorder},
+    rng::GameRng,
+    GamePlugin,
+};
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    /// Seed of the run, the same seed and the same inputs always yield the same run
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Record the decisions of the player to a replay file
+    #[arg(long, conflicts_with = "replay")]
+    record: Option<PathBuf>,
+
+    /// Play a recorded replay file instead of the keyboard inputs
+    #[arg(long, conflicts_with = "seed")]
+    replay: Option<PathBuf>,
+
+    /// Allow to undo the last turns with F6, to test move interactions and AI tuning
+    #[arg(long, conflicts_with_all = ["record", "replay"])]
+    practice: bool,
+}
+
+fn main() -> AppExit {
+    let args = Args::parse();
+
+    let mut app = App::new();
+    if let Some(path) = args.replay {
+        let replay = match Replay::load(&path) {
+            Ok(replay) => replay,
+            Err(err) => {
+                eprintln!("Failed to load the replay {}: {err}", path.display());
+                return AppExit::error();
+            }
+        };
+        app.insert_resource(GameRng::from_seed(replay.seed))
+            .insert_resource(ReplayPlayback::from(replay));
+    } else if let Some(path) = args.record {
+        let seed = args.seed.unwrap_or_else(rand::random);
+        let recorder = match ReplayRecorder::new(&path, seed) {
+            Ok(recorder) => recorder,
+            Err(err) => {
+                eprintln!("Failed to create the replay {}: {err}", path.display());
+                return AppExit::error();
+            }
+        };
+        app.insert_resource(GameRng::from_seed(seed))
+            .insert_resource(recorder);
+    } else if let Some(seed) = args.seed {
+        app.insert_resource(GameRng::from_seed(seed));
+    }
+
+    if args.practice {
+        app.insert_resource(PracticeMode);
+    }
 
-fn main() {
-    App::new()
-        .insert_resource(Msaa::Off)
+    app.insert_resource(Msaa::Off)
         .insert_resource(AmbientLight {
             color: Color::default(),
             brightness: 1.,
@@ -13,6 +72,8 @@ fn main() {
                 .set(AssetPlugin {
                     mode: AssetMode::Unprocessed,
                     meta_check: AssetMetaCheck::Always,
+                    // Reload the assets rewritten by `assets_builder --watch`
+                    watch_for_changes_override: Some(cfg!(debug_assertions)),
                     ..default()
                 })
                 .set(WindowPlugin {
@@ -29,5 +90,5 @@ fn main() {
                 .set(ImagePlugin::default_nearest()),
         )
         .add_plugins(GamePlugin)
-        .run();
+        .run()
 }
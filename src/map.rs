@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::GameState;
@@ -79,20 +80,33 @@ pub struct Position(pub IVec2);
 #[derive(Component)]
 pub struct Tilemap;
 
-#[derive(Copy, Clone, Debug, Ord, PartialOrd, PartialEq, Eq, Hash)]
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum EnvironmentType {
     Water,
     Lava,
 }
 
-#[derive(Copy, Clone, Debug, Ord, PartialOrd, PartialEq, Eq, Hash)]
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum TerrainType {
     Ground,
     Wall,
     Environment(EnvironmentType), // Water / Lava
 }
 
-#[derive(Copy, Clone, Debug, Ord, PartialOrd, PartialEq, Eq, Hash, Deref, DerefMut)]
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Ord,
+    PartialOrd,
+    PartialEq,
+    Eq,
+    Hash,
+    Deref,
+    DerefMut,
+    Serialize,
+    Deserialize,
+)]
 pub struct TerrainData {
     pub r#type: TerrainType,
 }
@@ -100,7 +114,7 @@ pub struct TerrainData {
 #[derive(Component, Debug)]
 pub struct Tile(pub TerrainData);
 
-fn spawn_map(mut commands: Commands, mut current_map: ResMut<GameMap>) {
+pub(crate) fn spawn_map(mut commands: Commands, mut current_map: ResMut<GameMap>) {
     let tilemap = commands
         .spawn((Tilemap, Name::new("Tilemap"), SpatialBundle { ..default() }))
         .id();
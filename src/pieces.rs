@@ -30,6 +30,7 @@ pub struct Piece {
 pub enum PieceKind {
     Player,
     Npc,
+    Trap,
 }
 
 #[derive(Component, Debug, Reflect)]
@@ -1,24 +1,29 @@
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
-use char_animation::anim_key::AnimKey;
 use char_animation::orientation::Orientation;
 use leafwing_input_manager::action_state::ActionState;
 use leafwing_input_manager::input_map::InputMap;
 use leafwing_input_manager::plugin::InputManagerPlugin;
 use leafwing_input_manager::{Actionlike, InputManagerBundle};
 use rand::Rng;
+use serde::{Deserialize, Serialize};
+use spell_data::SpellData;
 
-use crate::actions::destroy_wall_action::DestroyWallAction;
-use crate::actions::melee_hit_action::MeleeHitAction;
+use crate::actions::melee_hit_action::{MeleeHitAction, BASIC_ATTACK_DAMAGE};
 use crate::actions::skip_action::SkipAction;
 use crate::actions::spell_action::SpellAction;
 use crate::actions::walk_action::WalkAction;
 use crate::actions::{Action, ProcessingActionEvent};
+use crate::data::assets::spell_data::SpellDataLookup;
 use crate::faction::Faction;
 use crate::map::Position;
-use crate::move_type::MoveCategory;
 use crate::pieces::{Actor, FacingOrientation, Occupier, Piece, PieceKind};
 use crate::pokemons::Pokemon;
-use crate::spells::{ProjectileSpell, Spell, SpellCast, SpellHit, SpellType};
+use crate::replay::ReplayPlayback;
+use crate::rng::{GameRng, RngStream};
+use crate::spells::Spell;
+use crate::stats::Level;
+use crate::turn::TurnNumber;
 use crate::{GamePlayingSet, GameState};
 
 pub struct PlayerPlugin;
@@ -30,9 +35,16 @@ const DIR_KEY_MAPPING: [(PlayerAction, IVec2); 4] = [
     (PlayerAction::Right, IVec2 { x: 1, y: 0 }),
 ];
 
+/// The spells of the slots, by the file stem of their data
+const SPELL_SLOTS: [(PlayerAction, &str); 2] = [
+    (PlayerAction::SpellSlot1, "flamethrower"),
+    (PlayerAction::SpellSlot2, "rock_smash"),
+];
+
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<PlayerActionEvent>()
+            .add_event::<PlayerIntentEvent>()
             .add_plugins(InputManagerPlugin::<PlayerAction>::default())
             .add_systems(OnEnter(GameState::Initializing), spawn_player)
             .add_systems(Update, take_action.in_set(GamePlayingSet::Controls));
@@ -45,7 +57,11 @@ pub struct Player;
 #[derive(Event, Debug, Default)]
 pub struct PlayerActionEvent(pub Vec<Box<dyn Action>>);
 
-#[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect)]
+/// The decision of the player behind a `PlayerActionEvent`
+#[derive(Event, Debug)]
+pub struct PlayerIntentEvent(pub PlayerAction);
+
+#[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect, Serialize, Deserialize)]
 pub enum PlayerAction {
     Left,
     Right,
@@ -56,66 +72,102 @@ pub enum PlayerAction {
     SpellSlot2,
     SpellSlot3,
     SpellSlot4,
+    /// Answers of the recruitment prompt
+    AcceptRecruit,
+    DeclineRecruit,
 }
 
 fn spawn_player(
     mut commands: Commands,
     pokemon_char_assets: Res<crate::graphics::assets::pokemon_chara_assets::PokemonCharaAssets>,
+    mut rng: ResMut<GameRng>,
 ) {
     // TODO: This random Pokemon selection is temporary for testing purposes.
     // In the future, this will be replaced with player choice during game start/character creation.
-    let available_pokemon_ids: Vec<u32> = pokemon_char_assets.0.keys().cloned().collect();
+    let mut available_pokemon_ids: Vec<u32> = pokemon_char_assets.0.keys().cloned().collect();
+    available_pokemon_ids.sort();
 
     if available_pokemon_ids.is_empty() {
         panic!("No Pokemon character assets loaded! Check the assets/chara directory.");
     }
 
-    let starter_id =
-        available_pokemon_ids[rand::thread_rng().gen_range(0..available_pokemon_ids.len())];
+    let starter_id = available_pokemon_ids[rng
+        .stream(RngStream::MapGen)
+        .random_range(0..available_pokemon_ids.len())];
 
     info!("Starting with Pokemon ID: {}", starter_id);
 
-    commands.spawn((
-        Name::new("Player"),
-        FacingOrientation(Orientation::South),
-        Pokemon {
-            id: starter_id,
-            form_index: 0,
-        },
-        Faction::Player,
-        Player,
-        Occupier,
-        Actor,
-        Piece {
-            kind: PieceKind::Player,
-        },
-        Position(IVec2::new(4, 4)),
-        InputManagerBundle::<PlayerAction> {
-            action_state: ActionState::default(),
-            input_map: InputMap::new([
-                (PlayerAction::Skip, KeyCode::Space),
-                (PlayerAction::Skip, KeyCode::Space),
-                (PlayerAction::Up, KeyCode::KeyW),
-                (PlayerAction::Up, KeyCode::ArrowUp),
-                (PlayerAction::Down, KeyCode::KeyS),
-                (PlayerAction::Down, KeyCode::ArrowDown),
-                (PlayerAction::Left, KeyCode::KeyA),
-                (PlayerAction::Left, KeyCode::ArrowLeft),
-                (PlayerAction::Right, KeyCode::KeyD),
-                (PlayerAction::Right, KeyCode::ArrowRight),
-                (PlayerAction::SpellSlot1, KeyCode::Digit1),
-                (PlayerAction::SpellSlot2, KeyCode::Digit2),
-                (PlayerAction::SpellSlot3, KeyCode::Digit3),
-                (PlayerAction::SpellSlot4, KeyCode::Digit4),
-            ]),
-        },
-    ));
+    commands.spawn(PlayerBundle::new(starter_id, IVec2::new(4, 4)));
+}
+
+#[derive(Bundle)]
+pub struct PlayerBundle {
+    name: Name,
+    facing_orientation: FacingOrientation,
+    pokemon: Pokemon,
+    level: Level,
+    faction: Faction,
+    player: Player,
+    occupier: Occupier,
+    actor: Actor,
+    piece: Piece,
+    position: Position,
+    input_manager: InputManagerBundle<PlayerAction>,
 }
 
-fn take_action(
+impl PlayerBundle {
+    pub fn new(pokemon_id: u32, position: IVec2) -> Self {
+        Self {
+            name: Name::new("Player"),
+            facing_orientation: FacingOrientation(Orientation::South),
+            pokemon: Pokemon {
+                id: pokemon_id,
+                form_index: 0,
+            },
+            level: Level::default(),
+            faction: Faction::Player,
+            player: Player,
+            occupier: Occupier,
+            actor: Actor,
+            piece: Piece {
+                kind: PieceKind::Player,
+            },
+            position: Position(position),
+            input_manager: InputManagerBundle::<PlayerAction> {
+                action_state: ActionState::default(),
+                input_map: InputMap::new([
+                    (PlayerAction::Skip, KeyCode::Space),
+                    (PlayerAction::Skip, KeyCode::Space),
+                    (PlayerAction::Up, KeyCode::KeyW),
+                    (PlayerAction::Up, KeyCode::ArrowUp),
+                    (PlayerAction::Down, KeyCode::KeyS),
+                    (PlayerAction::Down, KeyCode::ArrowDown),
+                    (PlayerAction::Left, KeyCode::KeyA),
+                    (PlayerAction::Left, KeyCode::ArrowLeft),
+                    (PlayerAction::Right, KeyCode::KeyD),
+                    (PlayerAction::Right, KeyCode::ArrowRight),
+                    (PlayerAction::SpellSlot1, KeyCode::Digit1),
+                    (PlayerAction::SpellSlot2, KeyCode::Digit2),
+                    (PlayerAction::SpellSlot3, KeyCode::Digit3),
+                    (PlayerAction::SpellSlot4, KeyCode::Digit4),
+                    (PlayerAction::AcceptRecruit, KeyCode::Enter),
+                    (PlayerAction::DeclineRecruit, KeyCode::Escape),
+                ]),
+            },
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn take_action(
     player_query: Query<(Entity, &ActionState<PlayerAction>, &Position), With<Player>>,
+    mut playback: Option<ResMut<ReplayPlayback>>,
+    turn_number: Res<TurnNumber>,
+    spell_slots: SpellSlots,
     mut ev_processing_action: EventReader<ProcessingActionEvent>,
     mut ev_action: EventWriter<PlayerActionEvent>,
+    mut ev_intent: EventWriter<PlayerIntentEvent>,
+    mut commands: Commands,
 ) {
     if ev_processing_action.read().len() > 0 {
         // info!("Player can take action");
@@ -127,64 +179,92 @@ fn take_action(
         return;
     };
 
-    for (key, dir) in DIR_KEY_MAPPING {
-        if !action_state.pressed(&key) {
-            continue;
-        }
-        let target = position.0 + dir;
+    // A replay feeds the recorded intents instead of the keyboard ones
+    let intent = match playback.as_mut() {
+        Some(playback) => playback.next_intent(turn_number.0, &mut commands),
+        None => get_keyboard_intent(action_state),
+    };
+
+    let Some(intent) = intent else {
+        return;
+    };
+    let spell = spell_slots.spell(intent);
+    let Some(actions) = get_intent_actions(intent, entity, position.0, spell) else {
+        return;
+    };
+
+    info!("Send player action event");
+    ev_intent.send(PlayerIntentEvent(intent));
+    ev_action.send(PlayerActionEvent(actions));
+}
+
+fn get_keyboard_intent(action_state: &ActionState<PlayerAction>) -> Option<PlayerAction> {
+    DIR_KEY_MAPPING
+        .iter()
+        .map(|(key, _)| *key)
+        .chain([
+            PlayerAction::SpellSlot1,
+            PlayerAction::SpellSlot2,
+            PlayerAction::Skip,
+        ])
+        .find(|key| action_state.pressed(key))
+}
+
+#[derive(SystemParam)]
+pub(crate) struct SpellSlots<'w> {
+    spell_data_lookup: Res<'w, SpellDataLookup>,
+    spell_data_assets: Res<'w, Assets<SpellData>>,
+}
+
+impl SpellSlots<'_> {
+    /// The spell of a slot, `None` for the other intents or when its data is missing
+    fn spell(&self, intent: PlayerAction) -> Option<Spell> {
+        let (_, spell_name) = SPELL_SLOTS.iter().find(|(slot, _)| *slot == intent)?;
+        let Some(spell_data) = self
+            .spell_data_lookup
+            .0
+            .get(*spell_name)
+            .and_then(|handle| self.spell_data_assets.get(handle))
+        else {
+            warn!("Spell data not found for {spell_name}");
+            return None;
+        };
+        Some(Spell::from_data(spell_data))
+    }
+}
+
+fn get_intent_actions(
+    intent: PlayerAction,
+    entity: Entity,
+    position: IVec2,
+    spell: Option<Spell>,
+) -> Option<Vec<Box<dyn Action>>> {
+    if let Some((_, dir)) = DIR_KEY_MAPPING.iter().find(|(key, _)| *key == intent) {
+        let target = position + *dir;
 
         let walk_action = Box::new(WalkAction {
             entity,
-            from: position.0,
+            from: position,
             to: target,
         }) as Box<dyn Action>;
 
         let attack_action = Box::new(MeleeHitAction {
             attacker: entity,
-            damage: 1,
-            target,
-        }) as Box<dyn Action>;
-
-        let destroy_wall = Box::new(DestroyWallAction {
-            instigator: entity,
+            damage: BASIC_ATTACK_DAMAGE,
             target,
         }) as Box<dyn Action>;
 
-        info!("Send player action event");
-        ev_action.send(PlayerActionEvent(vec![
-            walk_action,
-            attack_action,
-            destroy_wall,
-        ]));
-        return;
-    }
-
-    if action_state.pressed(&PlayerAction::SpellSlot1) {
-        let action = Box::new(SpellAction {
-            caster: entity,
-            spell: Spell {
-                name: "Flamethrower",
-                range: 1..=3,
-                spell_type: SpellType::Projectile(ProjectileSpell {
-                    visual_effect: "Flamethrower_2",
-                }),
-                hit: SpellHit {
-                    visual_effect: "Flamethrower",
-                    damage: 1,
-                    move_type: MoveCategory::Special,
-                },
-                cast: SpellCast {
-                    visual_effect: "Circle_Small_Blue_In",
-                    animation: AnimKey::Shoot,
-                }, // Damage visual effect: Hit_Neutral
-                   // Cast visual effect: Circle_Small_Blue_Out
-            },
-        });
-        ev_action.send(PlayerActionEvent(vec![action]));
+        return Some(vec![walk_action, attack_action]);
     }
 
-    if action_state.pressed(&PlayerAction::Skip) {
-        let action = Box::new(SkipAction);
-        ev_action.send(PlayerActionEvent(vec![action]));
+    match intent {
+        PlayerAction::SpellSlot1 | PlayerAction::SpellSlot2 => spell.map(|spell| {
+            vec![Box::new(SpellAction {
+                caster: entity,
+                spell,
+            }) as Box<dyn Action>]
+        }),
+        PlayerAction::Skip => Some(vec![Box::new(SkipAction { entity })]),
+        _ => None,
     }
 }
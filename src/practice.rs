@@ -0,0 +1,156 @@
+use bevy::{ecs::event::ManualEventReader, prelude::*};
+
+use crate::{
+    actions::{ActionQueue, RunningAction},
+    player::{take_action, PlayerActionEvent},
+    save::RunSnapshot,
+    turn::TurnNumber,
+    GamePlayingSet,
+};
+
+/// Number of turns that can be undone
+const MAX_UNDO_TURNS: usize = 100;
+
+pub struct PracticePlugin;
+
+impl Plugin for PracticePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PracticeHistory>()
+            .add_systems(
+                Update,
+                undo_turn_input
+                    .in_set(GamePlayingSet::Inputs)
+                    .run_if(resource_exists::<PracticeMode>),
+            )
+            .add_systems(
+                Update,
+                record_turn
+                    .after(take_action)
+                    .in_set(GamePlayingSet::Controls)
+                    .run_if(resource_exists::<PracticeMode>),
+            );
+    }
+}
+
+/// Keep a snapshot of every turn so they can be undone, to test move interactions and AI tuning
+#[derive(Resource, Debug, Default)]
+pub struct PracticeMode;
+
+/// The state of the run before each of the last turns, the most recent last
+#[derive(Resource, Default)]
+pub struct PracticeHistory(Vec<(u32, RunSnapshot)>);
+
+/// Snapshot the run before the actions of a new turn are executed
+fn record_turn(world: &mut World, mut reader: Local<ManualEventReader<PlayerActionEvent>>) {
+    let events = world.resource::<Events<PlayerActionEvent>>();
+    if reader.read(events).count() == 0 {
+        return;
+    }
+
+    push_turn(world);
+}
+
+fn push_turn(world: &mut World) {
+    let turn = world.resource::<TurnNumber>().0;
+    let snapshot = RunSnapshot::capture(world);
+
+    let mut history = world.resource_mut::<PracticeHistory>();
+    history.0.push((turn, snapshot));
+    if history.0.len() > MAX_UNDO_TURNS {
+        history.0.remove(0);
+    }
+}
+
+/// Roll back one turn each time the key is pressed
+fn undo_turn_input(world: &mut World) {
+    if !world
+        .resource::<ButtonInput<KeyCode>>()
+        .just_pressed(KeyCode::F6)
+    {
+        return;
+    }
+
+    // Only undo between turns
+    let is_running_action = world
+        .query_filtered::<(), With<RunningAction>>()
+        .iter(world)
+        .next()
+        .is_some();
+    if is_running_action || !world.resource::<ActionQueue>().0.is_empty() {
+        return;
+    }
+
+    if !undo_turn(world) {
+        info!("No turn left to undo");
+    }
+}
+
+/// Restore the run as it was before the last turn, returns `false` if there is nothing to undo
+fn undo_turn(world: &mut World) -> bool {
+    let Some((turn, snapshot)) = world.resource_mut::<PracticeHistory>().0.pop() else {
+        return false;
+    };
+
+    snapshot.restore(world);
+    world.resource_mut::<TurnNumber>().0 = turn;
+    info!("Turn {} undone", turn + 1);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use crate::{
+        ai::NPCBundle,
+        faction::Faction,
+        map::Position,
+        player::PlayerBundle,
+        stats::Health,
+        test_harness::TestWorld,
+        turn::{TurnNumber, TurnOrder},
+    };
+
+    use super::{push_turn, undo_turn, PracticeHistory};
+
+    #[test]
+    fn undo_restores_a_despawned_npc() {
+        let mut test_world = TestWorld::new(TestWorld::map(5, 5, &[]));
+        let world = &mut test_world.world;
+        world.init_resource::<PracticeHistory>();
+        world.init_resource::<TurnNumber>();
+
+        let player = world.spawn(PlayerBundle::new(25, IVec2::new(1, 1))).id();
+        let npc = world
+            .spawn(NPCBundle::new(
+                "NPC".to_string(),
+                IVec2::new(3, 3),
+                Faction::Foe,
+            ))
+            .insert(Health { value: 4, max: 10 })
+            .id();
+        world.resource_mut::<TurnOrder>().0.extend([player, npc]);
+
+        push_turn(world);
+        world.resource_mut::<TurnNumber>().0 = 1;
+        world.get_mut::<Position>(player).unwrap().0 = IVec2::new(2, 1);
+        world.despawn(npc);
+        world.resource_mut::<TurnOrder>().0.retain(|e| *e != npc);
+
+        assert!(undo_turn(world));
+        assert!(!undo_turn(world));
+
+        assert_eq!(world.resource::<TurnNumber>().0, 0);
+        let turn_order = world.resource::<TurnOrder>().0.clone();
+        assert_eq!(turn_order.len(), 2);
+        assert_eq!(
+            world.get::<Position>(turn_order[0]).unwrap().0,
+            IVec2::new(1, 1)
+        );
+        assert_eq!(
+            world.get::<Position>(turn_order[1]).unwrap().0,
+            IVec2::new(3, 3)
+        );
+        assert_eq!(world.get::<Health>(turn_order[1]).unwrap().value, 4);
+    }
+}
@@ -0,0 +1,159 @@
+use bevy::prelude::*;
+use leafwing_input_manager::action_state::ActionState;
+use pokemon_data::PokemonData;
+use rand::Rng;
+
+use crate::{
+    actions::{leave_action::LeaveAction, ActionQueue, QueuedAction},
+    faction::Faction,
+    player::{Player, PlayerAction, PlayerIntentEvent},
+    replay::ReplayPlayback,
+    rng::{GameRng, RngStream},
+    stats::Level,
+    turn::TurnNumber,
+    GamePlayingSet,
+};
+
+pub const MAX_PARTY_SIZE: usize = 4;
+/// Chance bonus (in percent) for each level the recruiter has above the recruit
+const LEVEL_DIFFERENCE_BONUS: i32 = 5;
+/// Chance penalty (in percent) for each team member besides the leader
+const PARTY_MEMBER_PENALTY: i32 = 5;
+
+pub struct RecruitmentPlugin;
+
+impl Plugin for RecruitmentPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RecruitmentPrompt>()
+            .add_event::<RecruitmentAnsweredEvent>()
+            .add_systems(
+                Update,
+                answer_recruitment_prompt.in_set(GamePlayingSet::Inputs),
+            );
+    }
+}
+
+/// The defeated foe waiting for the player to accept it in the team
+#[derive(Resource, Default, Debug)]
+pub struct RecruitmentPrompt {
+    pub recruit: Option<Entity>,
+}
+
+impl RecruitmentPrompt {
+    pub fn is_pending(&self) -> bool {
+        self.recruit.is_some()
+    }
+}
+
+#[derive(Event, Debug)]
+pub struct RecruitmentAnsweredEvent {
+    pub recruit: Entity,
+    pub accepted: bool,
+}
+
+/// Recruitment chance in percent
+pub fn recruitment_chance(join_rate: i32, level_difference: i32, party_size: usize) -> i32 {
+    if party_size >= MAX_PARTY_SIZE {
+        return 0;
+    }
+
+    let party_penalty = party_size.saturating_sub(1) as i32 * PARTY_MEMBER_PENALTY;
+
+    (join_rate + level_difference * LEVEL_DIFFERENCE_BONUS - party_penalty).clamp(0, 100)
+}
+
+/// Roll whether the defeated `target` asks to join the team of the `recruiter`
+pub fn roll_recruitment(world: &mut World, recruiter: Entity, target: Entity) -> bool {
+    let is_team_recruiter = world
+        .get::<Faction>(recruiter)
+        .is_some_and(|faction| faction.is_team());
+    let is_foe_target = world.get::<Faction>(target) == Some(&Faction::Foe);
+    if !is_team_recruiter || !is_foe_target {
+        return false;
+    }
+
+    let Some(join_rate) = world
+        .get::<Handle<PokemonData>>(target)
+        .and_then(|handle| world.resource::<Assets<PokemonData>>().get(handle))
+        .map(|data| data.join_rate)
+    else {
+        return false;
+    };
+
+    let recruiter_level = world.get::<Level>(recruiter).copied().unwrap_or_default();
+    let target_level = world.get::<Level>(target).copied().unwrap_or_default();
+    let level_difference = recruiter_level.0 as i32 - target_level.0 as i32;
+
+    let party_size = world
+        .query::<&Faction>()
+        .iter(world)
+        .filter(|faction| faction.is_team())
+        .count();
+
+    let chance = recruitment_chance(join_rate, level_difference, party_size);
+    info!("Recruitment chance of {:?}: {}%", target, chance);
+
+    world
+        .resource_mut::<GameRng>()
+        .stream(RngStream::Combat)
+        .random_range(0..100)
+        < chance
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn answer_recruitment_prompt(
+    player_query: Query<&ActionState<PlayerAction>, With<Player>>,
+    mut playback: Option<ResMut<ReplayPlayback>>,
+    turn_number: Res<TurnNumber>,
+    mut prompt: ResMut<RecruitmentPrompt>,
+    mut query: Query<&mut Faction>,
+    mut action_queue: ResMut<ActionQueue>,
+    mut ev_answered: EventWriter<RecruitmentAnsweredEvent>,
+    mut ev_intent: EventWriter<PlayerIntentEvent>,
+    mut commands: Commands,
+) {
+    let Some(recruit) = prompt.recruit else {
+        return;
+    };
+
+    // A replay feeds the recorded answer instead of the keyboard one
+    let intent = match playback.as_mut() {
+        Some(playback) => playback.next_intent(turn_number.0, &mut commands),
+        None => player_query.get_single().ok().and_then(|action_state| {
+            [PlayerAction::AcceptRecruit, PlayerAction::DeclineRecruit]
+                .into_iter()
+                .find(|answer| action_state.just_pressed(answer))
+        }),
+    };
+    let Some(intent) = intent else {
+        return;
+    };
+    let accepted = match intent {
+        PlayerAction::AcceptRecruit => true,
+        PlayerAction::DeclineRecruit => false,
+        _ => {
+            warn!("Replay desync: {intent:?} played as the recruitment answer");
+            return;
+        }
+    };
+
+    // Recorded like the other decisions of the player
+    ev_intent.send(PlayerIntentEvent(intent));
+    prompt.recruit = None;
+    ev_answered.send(RecruitmentAnsweredEvent { recruit, accepted });
+
+    let Ok(mut faction) = query.get_mut(recruit) else {
+        return;
+    };
+
+    if accepted {
+        info!("{:?} joined the team", recruit);
+        *faction = Faction::Friend;
+    } else {
+        // The declined foe leaves the floor right after the prompt
+        action_queue.0.push_front(QueuedAction {
+            entity: recruit,
+            performable_actions: vec![Box::new(LeaveAction { entity: recruit })],
+        });
+    }
+}
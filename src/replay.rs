@@ -0,0 +1,317 @@
+use std::{
+    collections::VecDeque,
+    fs::{self, File},
+    io::{self, Write},
+    path::Path,
+};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    player::{take_action, PlayerAction, PlayerIntentEvent},
+    turn::TurnNumber,
+    GamePlayingSet,
+};
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            record_intents
+                .after(take_action)
+                .in_set(GamePlayingSet::Controls)
+                .run_if(resource_exists::<ReplayRecorder>),
+        );
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum ReplayError {
+    #[error("Could not access the replay file: {0}")]
+    Io(#[from] io::Error),
+    #[error("Could not serialize the replay: {0}")]
+    Serialize(#[from] ron::Error),
+    #[error("Could not deserialize the replay line {line}: {source}")]
+    Deserialize {
+        line: usize,
+        source: ron::de::SpannedError,
+    },
+    #[error("The replay file is empty")]
+    MissingHeader,
+}
+
+/// The seed of the run and every decision of the player, enough to play the run again
+///
+/// Stored as a header line with the seed followed by one line per turn, so the turns are
+/// appended to the file as they are played
+#[derive(Debug, Default)]
+pub struct Replay {
+    pub seed: u64,
+    pub turns: Vec<RecordedTurn>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ReplayHeader {
+    seed: u64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct RecordedTurn {
+    pub turn: u32,
+    pub action: PlayerAction,
+}
+
+impl Replay {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ReplayError> {
+        let buffer = fs::read_to_string(path)?;
+        let mut lines = buffer
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| (index + 1, line));
+
+        let (line, header) = lines.next().ok_or(ReplayError::MissingHeader)?;
+        let header: ReplayHeader =
+            ron::from_str(header).map_err(|source| ReplayError::Deserialize { line, source })?;
+        let turns = lines
+            .map(|(line, turn)| {
+                ron::from_str(turn).map_err(|source| ReplayError::Deserialize { line, source })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            seed: header.seed,
+            turns,
+        })
+    }
+}
+
+/// Append every decision of the player to the replay file
+#[derive(Resource)]
+pub struct ReplayRecorder {
+    file: File,
+}
+
+impl ReplayRecorder {
+    pub fn new(path: impl AsRef<Path>, seed: u64) -> Result<Self, ReplayError> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut recorder = Self {
+            file: File::create(path)?,
+        };
+        recorder.write_line(&ReplayHeader { seed })?;
+        Ok(recorder)
+    }
+
+    /// Written right away, so a crash doesn't lose the replay
+    pub fn record(&mut self, turn: RecordedTurn) -> Result<(), ReplayError> {
+        self.write_line(&turn)
+    }
+
+    fn write_line(&mut self, value: &impl Serialize) -> Result<(), ReplayError> {
+        let mut line = ron::to_string(value)?;
+        line.push('\n');
+        self.file.write_all(line.as_bytes())?;
+        Ok(())
+    }
+}
+
+/// Feed the recorded decisions back instead of the keyboard ones
+#[derive(Resource)]
+pub struct ReplayPlayback {
+    turns: VecDeque<RecordedTurn>,
+}
+
+impl From<Replay> for ReplayPlayback {
+    fn from(replay: Replay) -> Self {
+        Self {
+            turns: replay.turns.into(),
+        }
+    }
+}
+
+impl ReplayPlayback {
+    /// The playback is removed once the replay is finished, the keyboard takes over
+    pub fn next_intent(&mut self, turn: u32, commands: &mut Commands) -> Option<PlayerAction> {
+        let Some(recorded_turn) = self.turns.pop_front() else {
+            info!("Replay finished");
+            commands.remove_resource::<ReplayPlayback>();
+            return None;
+        };
+        if recorded_turn.turn != turn {
+            warn!(
+                "Replay desync: recorded turn {} played at turn {}",
+                recorded_turn.turn, turn
+            );
+        }
+        Some(recorded_turn.action)
+    }
+}
+
+fn record_intents(
+    mut ev_intent: EventReader<PlayerIntentEvent>,
+    turn_number: Res<TurnNumber>,
+    mut recorder: ResMut<ReplayRecorder>,
+) {
+    for intent in ev_intent.read() {
+        let turn = RecordedTurn {
+            turn: turn_number.0,
+            action: intent.0,
+        };
+        if let Err(err) = recorder.record(turn) {
+            error!("Failed to save the replay: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use bevy::{
+        ecs::{event::Events, system::RunSystemOnce},
+        prelude::*,
+    };
+
+    use crate::{
+        actions::ActionQueue,
+        faction::Faction,
+        player::{PlayerAction, PlayerIntentEvent},
+        recruitment::{answer_recruitment_prompt, RecruitmentAnsweredEvent, RecruitmentPrompt},
+        save::{RunSnapshot, SaveFile, SavePlugin},
+        simulation::SimulationPlugin,
+        turn::TurnNumber,
+    };
+
+    use super::{
+        record_intents, RecordedTurn, Replay, ReplayError, ReplayPlayback, ReplayRecorder,
+    };
+
+    #[test]
+    fn loads_the_recorded_turns() {
+        let path = std::env::temp_dir().join(format!("replay_{}.ron", std::process::id()));
+        let mut recorder = ReplayRecorder::new(&path, 1234).unwrap();
+        for (turn, action) in [(1, PlayerAction::Left), (2, PlayerAction::SpellSlot2)] {
+            recorder.record(RecordedTurn { turn, action }).unwrap();
+        }
+
+        let replay = Replay::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(replay.seed, 1234);
+        let turns = replay
+            .turns
+            .iter()
+            .map(|turn| (turn.turn, turn.action))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            turns,
+            [(1, PlayerAction::Left), (2, PlayerAction::SpellSlot2)]
+        );
+    }
+
+    #[test]
+    fn reports_a_corrupted_line() {
+        let path = std::env::temp_dir().join(format!("corrupted_{}.ron", std::process::id()));
+        fs::write(&path, "(seed: 1)\n(turn: 1, action: Left)\n(turn: 2, act").unwrap();
+
+        let result = Replay::load(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert!(matches!(
+            result,
+            Err(ReplayError::Deserialize { line: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn replays_and_records_the_recruitment_answer() {
+        let path = std::env::temp_dir().join(format!("recruitment_{}.ron", std::process::id()));
+        let mut world = World::new();
+        world.insert_resource(TurnNumber(3));
+        world.init_resource::<ActionQueue>();
+        world.init_resource::<Events<RecruitmentAnsweredEvent>>();
+        world.init_resource::<Events<PlayerIntentEvent>>();
+        let recruit = world.spawn(Faction::Foe).id();
+        world.insert_resource(RecruitmentPrompt {
+            recruit: Some(recruit),
+        });
+        world.insert_resource(ReplayPlayback::from(Replay {
+            seed: 1234,
+            turns: vec![RecordedTurn {
+                turn: 3,
+                action: PlayerAction::AcceptRecruit,
+            }],
+        }));
+        world.insert_resource(ReplayRecorder::new(&path, 1234).unwrap());
+
+        world.run_system_once(answer_recruitment_prompt);
+        world.run_system_once(record_intents);
+
+        assert!(!world.resource::<RecruitmentPrompt>().is_pending());
+        assert_eq!(world.get::<Faction>(recruit), Some(&Faction::Friend));
+
+        world.remove_resource::<ReplayRecorder>();
+        let replay = Replay::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        let turns = replay
+            .turns
+            .iter()
+            .map(|turn| (turn.turn, turn.action))
+            .collect::<Vec<_>>();
+        assert_eq!(turns, [(3, PlayerAction::AcceptRecruit)]);
+    }
+
+    /// A first frame of the simulation with a suspended run waiting in `save_file`
+    fn start_run(save_file: &Path, insert_replay: impl FnOnce(&mut App)) -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, SimulationPlugin, SavePlugin))
+            .init_resource::<ButtonInput<KeyCode>>()
+            .insert_resource(SaveFile(save_file.to_path_buf()));
+        insert_replay(&mut app);
+        app.update();
+        app
+    }
+
+    #[test]
+    fn replays_ignore_the_suspended_run() {
+        let save_file = std::env::temp_dir().join(format!("suspended_{}.ron", std::process::id()));
+        let replay_file = std::env::temp_dir().join(format!("ignored_{}.ron", std::process::id()));
+
+        let mut saved_app = App::new();
+        saved_app.add_plugins((MinimalPlugins, SimulationPlugin));
+        for _ in 0..20 {
+            saved_app.update();
+        }
+        let saved_turn = saved_app.world().resource::<TurnNumber>().0;
+        RunSnapshot::capture(saved_app.world_mut())
+            .save(&save_file)
+            .unwrap();
+
+        let played_app = start_run(&save_file, |app| {
+            app.insert_resource(ReplayPlayback::from(Replay::default()));
+        });
+        let recorded_app = start_run(&save_file, |app| {
+            app.insert_resource(ReplayRecorder::new(&replay_file, 1234).unwrap());
+        });
+
+        let is_save_kept = save_file.exists();
+        fs::remove_file(&save_file).unwrap();
+        fs::remove_file(&replay_file).unwrap();
+        assert!(
+            is_save_kept,
+            "The suspended run is kept for a normal launch"
+        );
+        for app in [played_app, recorded_app] {
+            assert!(app.world().resource::<TurnNumber>().0 < saved_turn);
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use bevy::prelude::*;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
+use strum::{EnumCount, EnumIter, IntoEnumIterator};
+
+pub struct RngPlugin;
+
+impl Plugin for RngPlugin {
+    fn build(&self, app: &mut App) {
+        // The seed may already be set from the command line
+        app.init_resource::<GameRng>()
+            .add_systems(Startup, log_seed);
+    }
+}
+
+fn log_seed(rng: Res<GameRng>) {
+    info!("Run seed: {}", rng.seed());
+}
+
+/// Independent random streams, so that e.g. a change in the AI doesn't alter the map generation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, EnumCount)]
+pub enum RngStream {
+    MapGen,
+    AI,
+    Combat,
+    Loot,
+}
+
+/// Every random call of a run goes through this resource, the same seed and the same inputs
+/// always yield the same run
+#[derive(Resource)]
+pub struct GameRng {
+    seed: u64,
+    streams: [ChaCha12Rng; RngStream::COUNT],
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::from_seed(rand::random())
+    }
+}
+
+impl GameRng {
+    pub fn from_seed(seed: u64) -> Self {
+        let mut master = ChaCha12Rng::seed_from_u64(seed);
+        let streams = RngStream::iter()
+            .map(|_| ChaCha12Rng::seed_from_u64(master.next_u64()))
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap();
+
+        Self { seed, streams }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn stream(&mut self, stream: RngStream) -> &mut ChaCha12Rng {
+        &mut self.streams[stream as usize]
+    }
+
+    /// The streams are all derived from the seed, so only how far each one went is kept
+    pub fn save_state(&self) -> GameRngState {
+        GameRngState {
+            seed: self.seed,
+            streams: self
+                .streams
+                .iter()
+                .map(|rng| rng.get_word_pos() as u64)
+                .collect(),
+        }
+    }
+
+    pub fn restore_state(&mut self, state: &GameRngState) {
+        *self = Self::from_seed(state.seed);
+        for (rng, word_pos) in self.streams.iter_mut().zip(state.streams.iter()) {
+            rng.set_word_pos(*word_pos as u128);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameRngState {
+    pub seed: u64,
+    pub streams: Vec<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::RngCore;
+
+    use super::{GameRng, RngStream};
+
+    fn draw(rng: &mut GameRng) -> Vec<u64> {
+        [RngStream::MapGen, RngStream::AI, RngStream::Combat]
+            .into_iter()
+            .map(|stream| rng.stream(stream).next_u64())
+            .collect()
+    }
+
+    #[test]
+    fn saving_the_state_keeps_the_sequence() {
+        let mut rng = GameRng::from_seed(42);
+        let mut untouched = GameRng::from_seed(42);
+        draw(&mut rng);
+        draw(&mut untouched);
+
+        rng.save_state();
+
+        assert_eq!(draw(&mut rng), draw(&mut untouched));
+    }
+
+    #[test]
+    fn restoring_the_state_resumes_the_sequence() {
+        let mut rng = GameRng::from_seed(42);
+        draw(&mut rng);
+        let state = rng.save_state();
+
+        let mut restored = GameRng::from_seed(7);
+        restored.restore_state(&state);
+
+        assert_eq!(restored.seed(), 42);
+        assert_eq!(draw(&mut restored), draw(&mut rng));
+    }
+}
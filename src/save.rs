@@ -0,0 +1,518 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use bevy::{app::AppExit, ecs::system::RunSystemOnce, prelude::*};
+use char_animation::orientation::Orientation;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    actions::{ActionQueue, RunningAction},
+    ai::{spawn_npcs, NPCBundle},
+    evolution::KillCount,
+    faction::Faction,
+    map::{spawn_map, GameMap, Position, TerrainData, Tilemap},
+    pieces::{FacingOrientation, Piece},
+    player::{Player, PlayerBundle},
+    pokemons::Pokemon,
+    recruitment::RecruitmentPrompt,
+    replay::{ReplayPlayback, ReplayRecorder},
+    rng::{GameRng, GameRngState},
+    stats::{Experience, Health, Level, Stats},
+    status::{Immobilized, Poisoned},
+    traps::{spawn_traps, HiddenTrap, Trap, TrapBundle, TrapKind},
+    turn::{TurnNumber, TurnOrder},
+    GamePlayingSet, GameState,
+};
+
+const SAVE_FILE_PATH: &str = "saves/run.ron";
+
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SaveFile>()
+            .add_systems(
+                OnEnter(GameState::Playing),
+                // The freshly spawned pieces are replaced by the saved ones
+                resume_run
+                    .after(spawn_map)
+                    .after(spawn_traps)
+                    .after(spawn_npcs)
+                    // A replay runs from the seeded world, not from the suspended run
+                    .run_if(
+                        not(resource_exists::<ReplayPlayback>)
+                            .and_then(not(resource_exists::<ReplayRecorder>)),
+                    ),
+            )
+            .add_systems(Update, suspend_run.in_set(GamePlayingSet::Inputs));
+    }
+}
+
+/// Where the run is suspended to and resumed from
+#[derive(Resource, Debug, Clone)]
+pub struct SaveFile(pub PathBuf);
+
+impl Default for SaveFile {
+    fn default() -> Self {
+        Self(PathBuf::from(SAVE_FILE_PATH))
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum SaveError {
+    #[error("Could not access the save file: {0}")]
+    Io(#[from] io::Error),
+    #[error("Could not serialize the run: {0}")]
+    Serialize(#[from] ron::Error),
+    #[error("Could not deserialize the run: {0}")]
+    Deserialize(#[from] ron::de::SpannedError),
+}
+
+/// Everything needed to rebuild a run in progress
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RunSnapshot {
+    pub tiles: Vec<(IVec2, TerrainData)>,
+    pub pieces: Vec<PieceSnapshot>,
+    /// Indices in `pieces` of the actors, in turn order
+    pub turn_order: Vec<usize>,
+    pub rng: GameRngState,
+    #[serde(default)]
+    pub turn_number: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum PieceSnapshot {
+    Pokemon(PokemonSnapshot),
+    Trap(TrapSnapshot),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PokemonSnapshot {
+    pub name: String,
+    pub is_player: bool,
+    pub id: u32,
+    pub form_index: usize,
+    pub level: Level,
+    pub position: IVec2,
+    pub orientation: Orientation,
+    pub faction: Faction,
+    pub health: Option<Health>,
+    pub stats: Option<Stats>,
+    pub poisoned: Option<Poisoned>,
+    pub immobilized: Option<Immobilized>,
+    pub kill_count: Option<KillCount>,
+    pub experience: Option<Experience>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TrapSnapshot {
+    pub kind: TrapKind,
+    pub position: IVec2,
+    pub hidden: bool,
+}
+
+impl RunSnapshot {
+    pub fn capture(world: &mut World) -> Self {
+        let mut tiles = world
+            .resource::<GameMap>()
+            .tiles
+            .iter()
+            .map(|(position, tile)| (*position, *tile))
+            .collect::<Vec<_>>();
+        tiles.sort_by_key(|(position, _)| (position.x, position.y));
+
+        let entities = world
+            .query_filtered::<Entity, With<Piece>>()
+            .iter(world)
+            .collect::<Vec<_>>();
+
+        let mut pieces = vec![];
+        let mut piece_entities = vec![];
+        for entity in entities {
+            let Some(piece) = Self::capture_piece(world, entity) else {
+                continue;
+            };
+            pieces.push(piece);
+            piece_entities.push(entity);
+        }
+
+        let turn_order = world
+            .resource::<TurnOrder>()
+            .0
+            .iter()
+            .filter_map(|actor| piece_entities.iter().position(|e| e == actor))
+            .collect();
+
+        let rng = world.resource::<GameRng>().save_state();
+
+        Self {
+            tiles,
+            pieces,
+            turn_order,
+            rng,
+            turn_number: world
+                .get_resource::<TurnNumber>()
+                .map_or(0, |turn_number| turn_number.0),
+        }
+    }
+
+    fn capture_piece(world: &World, entity: Entity) -> Option<PieceSnapshot> {
+        let position = world.get::<Position>(entity)?.0;
+
+        if let Some(trap) = world.get::<Trap>(entity) {
+            return Some(PieceSnapshot::Trap(TrapSnapshot {
+                kind: trap.kind,
+                position,
+                hidden: world.get::<HiddenTrap>(entity).is_some(),
+            }));
+        }
+
+        let pokemon = world.get::<Pokemon>(entity)?;
+        Some(PieceSnapshot::Pokemon(PokemonSnapshot {
+            name: world
+                .get::<Name>(entity)
+                .map(|name| name.to_string())
+                .unwrap_or_default(),
+            is_player: world.get::<Player>(entity).is_some(),
+            id: pokemon.id,
+            form_index: pokemon.form_index,
+            level: world.get::<Level>(entity).copied().unwrap_or_default(),
+            position,
+            orientation: world
+                .get::<FacingOrientation>(entity)
+                .map(|orientation| orientation.0.clone())
+                .unwrap_or_default(),
+            faction: *world.get::<Faction>(entity)?,
+            health: world.get::<Health>(entity).cloned(),
+            stats: world.get::<Stats>(entity).cloned(),
+            poisoned: world.get::<Poisoned>(entity).cloned(),
+            immobilized: world.get::<Immobilized>(entity).cloned(),
+            kill_count: world.get::<KillCount>(entity).copied(),
+            experience: world.get::<Experience>(entity).copied(),
+        }))
+    }
+
+    /// Replace the current map and pieces with the ones of the snapshot
+    pub fn restore(self, world: &mut World) {
+        let previous_entities = world
+            .query_filtered::<Entity, Or<(With<Piece>, With<Tilemap>)>>()
+            .iter(world)
+            .collect::<Vec<_>>();
+        for entity in previous_entities {
+            despawn_with_children_recursive(world, entity);
+        }
+
+        world.resource_mut::<GameRng>().restore_state(&self.rng);
+        world.insert_resource(TurnNumber(self.turn_number));
+        world.resource_mut::<ActionQueue>().0.clear();
+        world.resource_mut::<RecruitmentPrompt>().recruit = None;
+
+        let mut map = world.resource_mut::<GameMap>();
+        map.tiles = self.tiles.into_iter().collect();
+        map.tiles_lookup.clear();
+        world.run_system_once(spawn_map);
+
+        let piece_entities = self
+            .pieces
+            .into_iter()
+            .map(|piece| match piece {
+                PieceSnapshot::Pokemon(pokemon) => Self::restore_pokemon(world, pokemon),
+                PieceSnapshot::Trap(trap) => {
+                    let mut entity = world.spawn(TrapBundle::new(trap.kind, trap.position));
+                    if !trap.hidden {
+                        entity.remove::<HiddenTrap>();
+                    }
+                    entity.id()
+                }
+            })
+            .collect::<Vec<_>>();
+
+        world.resource_mut::<TurnOrder>().0 = self
+            .turn_order
+            .into_iter()
+            .filter_map(|index| piece_entities.get(index).copied())
+            .collect();
+    }
+
+    fn restore_pokemon(world: &mut World, snapshot: PokemonSnapshot) -> Entity {
+        let mut entity = if snapshot.is_player {
+            world.spawn(PlayerBundle::new(snapshot.id, snapshot.position))
+        } else {
+            world.spawn(NPCBundle::new(
+                snapshot.name.clone(),
+                snapshot.position,
+                snapshot.faction,
+            ))
+        };
+
+        entity.insert((
+            Name::new(snapshot.name),
+            Pokemon {
+                id: snapshot.id,
+                form_index: snapshot.form_index,
+            },
+            snapshot.level,
+            snapshot.faction,
+            FacingOrientation(snapshot.orientation),
+        ));
+        if let Some(health) = snapshot.health {
+            entity.insert(health);
+        }
+        if let Some(stats) = snapshot.stats {
+            entity.insert(stats);
+        }
+        if let Some(poisoned) = snapshot.poisoned {
+            entity.insert(poisoned);
+        }
+        if let Some(immobilized) = snapshot.immobilized {
+            entity.insert(immobilized);
+        }
+        if let Some(kill_count) = snapshot.kill_count {
+            entity.insert(kill_count);
+        }
+        if let Some(experience) = snapshot.experience {
+            entity.insert(experience);
+        }
+
+        entity.id()
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), SaveError> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let buffer = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        fs::write(path, buffer)?;
+        Ok(())
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SaveError> {
+        let buffer = fs::read_to_string(path)?;
+        Ok(ron::from_str(&buffer)?)
+    }
+}
+
+/// Save the run and quit the game, the run is resumed on the next launch
+fn suspend_run(world: &mut World) {
+    if !world
+        .resource::<ButtonInput<KeyCode>>()
+        .just_pressed(KeyCode::F5)
+    {
+        return;
+    }
+
+    // Only save between turns
+    let is_running_action = world
+        .query_filtered::<(), With<RunningAction>>()
+        .iter(world)
+        .next()
+        .is_some();
+    if is_running_action || !world.resource::<ActionQueue>().0.is_empty() {
+        return;
+    }
+
+    let save_file = world.resource::<SaveFile>().0.clone();
+    let snapshot = RunSnapshot::capture(world);
+    if let Err(err) = snapshot.save(&save_file) {
+        error!("Failed to save the run: {err}");
+        return;
+    }
+
+    info!("Run saved to {}", save_file.display());
+    world.send_event(AppExit::Success);
+}
+
+/// A suspended run is consumed once resumed
+fn resume_run(world: &mut World) {
+    let save_file = world.resource::<SaveFile>().0.clone();
+    if !save_file.exists() {
+        return;
+    }
+
+    let snapshot = match RunSnapshot::load(&save_file) {
+        Ok(snapshot) => snapshot,
+        Err(err) => {
+            error!("Failed to load the saved run: {err}");
+            return;
+        }
+    };
+
+    snapshot.restore(world);
+
+    if let Err(err) = fs::remove_file(&save_file) {
+        warn!("Failed to remove the saved run: {err}");
+    }
+    info!("Run resumed from {}", save_file.display());
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use bevy::{
+        ecs::schedule::{LogLevel, ScheduleBuildSettings},
+        prelude::*,
+    };
+
+    use crate::{
+        actions::ActionQueue,
+        ai::NPCBundle,
+        faction::Faction,
+        map::{GameMap, Position, TerrainData, TerrainType},
+        pieces::{Piece, PieceKind},
+        player::PlayerBundle,
+        recruitment::RecruitmentPrompt,
+        rng::RngStream,
+        simulation::SimulationPlugin,
+        stats::Health,
+        status::Poisoned,
+        traps::{TrapBundle, TrapKind},
+        turn::{TurnNumber, TurnOrder},
+        GameState,
+    };
+
+    use super::{GameRng, RunSnapshot, SaveFile, SavePlugin};
+    use rand::RngCore;
+
+    /// A bare world holding the resources of a run, on a `size` x `size` ground map
+    fn run_world(size: i32) -> World {
+        let tiles = (0..size)
+            .flat_map(|x| (0..size).map(move |y| IVec2::new(x, y)))
+            .map(|position| {
+                let r#type = TerrainType::Ground;
+                (position, TerrainData { r#type })
+            })
+            .collect();
+
+        let mut world = World::new();
+        world.insert_resource(GameMap {
+            tiles,
+            tiles_lookup: HashMap::new(),
+        });
+        world.insert_resource(GameRng::from_seed(0));
+        world.init_resource::<ActionQueue>();
+        world.init_resource::<TurnOrder>();
+        world.init_resource::<RecruitmentPrompt>();
+        world
+    }
+
+    fn spawn_run(world: &mut World) {
+        let player = world
+            .spawn(PlayerBundle::new(25, IVec2::new(1, 1)))
+            .insert(Health { value: 7, max: 20 })
+            .id();
+        let npc = world
+            .spawn(NPCBundle::new(
+                "NPC".to_string(),
+                IVec2::new(3, 3),
+                Faction::Foe,
+            ))
+            .insert(Poisoned { remaining_turns: 2 })
+            .id();
+        world.spawn(TrapBundle::new(TrapKind::Spike, IVec2::new(2, 2)));
+        world.resource_mut::<TurnOrder>().0.extend([npc, player]);
+        world.insert_resource(TurnNumber(42));
+    }
+
+    #[test]
+    fn round_trips_a_run() {
+        let mut saved_world = run_world(6);
+        spawn_run(&mut saved_world);
+
+        let snapshot = RunSnapshot::capture(&mut saved_world);
+        let saved = ron::to_string(&snapshot).unwrap();
+
+        let mut world = run_world(2);
+        let snapshot: RunSnapshot = ron::from_str(&saved).unwrap();
+        snapshot.restore(&mut world);
+
+        assert_eq!(world.resource::<GameMap>().tiles.len(), 36);
+        assert_eq!(world.resource::<GameMap>().tiles_lookup.len(), 36);
+
+        let turn_order = world.resource::<TurnOrder>().0.clone();
+        assert_eq!(turn_order.len(), 2);
+        let npc = turn_order[0];
+        let player = turn_order[1];
+        assert_eq!(world.get::<Position>(npc).unwrap().0, IVec2::new(3, 3));
+        assert_eq!(world.get::<Poisoned>(npc).unwrap().remaining_turns, 2);
+        assert_eq!(world.get::<Position>(player).unwrap().0, IVec2::new(1, 1));
+        assert_eq!(world.get::<Health>(player).unwrap().value, 7);
+        assert_eq!(world.resource::<TurnNumber>().0, 42);
+
+        // Saving the restored run gives back the same snapshot
+        let resaved = ron::to_string(&RunSnapshot::capture(&mut world)).unwrap();
+        let saved_again = ron::to_string(&RunSnapshot::capture(&mut saved_world)).unwrap();
+        assert_eq!(resaved, saved_again);
+    }
+
+    #[test]
+    fn restored_rng_keeps_the_same_sequence() {
+        let mut saved_world = run_world(2);
+        let snapshot = RunSnapshot::capture(&mut saved_world);
+
+        let mut restored_world = run_world(2);
+        restored_world.insert_resource(GameRng::from_seed(1234));
+        snapshot.restore(&mut restored_world);
+
+        for stream in [RngStream::MapGen, RngStream::AI, RngStream::Combat] {
+            let expected = saved_world
+                .resource_mut::<GameRng>()
+                .stream(stream)
+                .next_u64();
+            let restored = restored_world
+                .resource_mut::<GameRng>()
+                .stream(stream)
+                .next_u64();
+            assert_eq!(restored, expected);
+        }
+    }
+
+    fn npc_count(app: &mut App) -> usize {
+        app.world_mut()
+            .query::<&Piece>()
+            .iter(app.world())
+            .filter(|piece| matches!(piece.kind, PieceKind::Npc))
+            .count()
+    }
+
+    #[test]
+    fn resuming_replaces_the_freshly_spawned_pieces() {
+        let save_file = std::env::temp_dir().join(format!("resume_{}.ron", std::process::id()));
+
+        let mut saved_app = App::new();
+        saved_app.add_plugins((MinimalPlugins, SimulationPlugin));
+        for _ in 0..20 {
+            saved_app.update();
+        }
+        let saved_npcs = npc_count(&mut saved_app);
+        let saved_turn = saved_app.world().resource::<TurnNumber>().0;
+        assert!(saved_turn > 1);
+        RunSnapshot::capture(saved_app.world_mut())
+            .save(&save_file)
+            .unwrap();
+
+        let mut resumed_app = App::new();
+        resumed_app
+            .add_plugins((MinimalPlugins, SimulationPlugin, SavePlugin))
+            .init_resource::<ButtonInput<KeyCode>>()
+            .insert_resource(SaveFile(save_file.clone()))
+            // Restoring while the fresh pieces are still being spawned would keep them both
+            .edit_schedule(OnEnter(GameState::Playing), |schedule| {
+                schedule.set_build_settings(ScheduleBuildSettings {
+                    ambiguity_detection: LogLevel::Error,
+                    ..default()
+                });
+            });
+        resumed_app.update();
+
+        assert_eq!(npc_count(&mut resumed_app), saved_npcs);
+        // The first frame may already play a turn
+        assert!(resumed_app.world().resource::<TurnNumber>().0 >= saved_turn);
+        assert!(!save_file.exists(), "The resumed run is consumed");
+    }
+}
@@ -0,0 +1,166 @@
+use bevy::{prelude::*, state::app::StatesPlugin};
+use pokemon_data::PokemonData;
+
+use crate::{
+    actions::{ActionQueue, ActionsPlugin, RunningAction},
+    ai::AIPlugin,
+    configure_game_playing_sets,
+    map::MapPlugin,
+    pieces::PiecesPlugin,
+    player::PlayerActionEvent,
+    pokemons::Pokemon,
+    recruitment::RecruitmentPrompt,
+    rng::RngPlugin,
+    stats::{Stat, Stats, StatsPlugin},
+    turn::TurnPlugin,
+    GamePlayingSet, GameState,
+};
+
+/// Runs the turn, AI and action logic without any window, rendering or asset loading.
+///
+/// Meant to be added alongside the `MinimalPlugins`, the actions don't wait for any animation
+/// and a new turn starts as soon as the previous one is resolved. The pokemon data isn't
+/// loaded, every pokemon gets the `SimulationStats` instead.
+pub struct SimulationPlugin;
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            StatesPlugin,
+            AssetPlugin {
+                watch_for_changes_override: Some(false),
+                ..default()
+            },
+        ))
+        .init_asset::<PokemonData>()
+        .insert_state(GameState::Playing);
+        configure_game_playing_sets(app);
+
+        app.add_event::<PlayerActionEvent>()
+            .init_resource::<RecruitmentPrompt>()
+            .init_resource::<SimulationStats>()
+            .add_plugins((
+                RngPlugin,
+                MapPlugin,
+                PiecesPlugin,
+                StatsPlugin,
+                AIPlugin { follow_plans: true },
+                ActionsPlugin,
+                TurnPlugin,
+            ))
+            .add_systems(Update, add_simulation_stats)
+            .add_systems(Update, advance_turn.in_set(GamePlayingSet::Controls))
+            .add_systems(
+                Update,
+                resolve_running_actions.in_set(GamePlayingSet::Animations),
+            );
+    }
+}
+
+/// The base stats given to every pokemon of the simulation
+#[derive(Resource, Debug, Clone)]
+pub struct SimulationStats(pub Stats);
+
+impl Default for SimulationStats {
+    fn default() -> Self {
+        Self(Stats {
+            health: Stat::new(20),
+            attack: Stat::new(50),
+            special_attack: Stat::new(50),
+            defense: Stat::new(50),
+            special_defense: Stat::new(50),
+            speed: Stat::new(50),
+        })
+    }
+}
+
+/// Stand in for the stats computed from the pokemon data, their health follows
+fn add_simulation_stats(
+    query: Query<Entity, (With<Pokemon>, Without<Stats>)>,
+    stats: Res<SimulationStats>,
+    mut commands: Commands,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).insert(stats.0.clone());
+    }
+}
+
+/// Start a new turn, without any player action, once the previous one is resolved
+fn advance_turn(
+    query_running_action: Query<(), With<RunningAction>>,
+    action_queue: Res<ActionQueue>,
+    mut ev_action: EventWriter<PlayerActionEvent>,
+) {
+    if !query_running_action.is_empty() || !action_queue.0.is_empty() {
+        return;
+    }
+
+    ev_action.send(PlayerActionEvent::default());
+}
+
+/// There is no animation to wait for
+fn resolve_running_actions(query: Query<Entity, With<RunningAction>>, mut commands: Commands) {
+    for entity in query.iter() {
+        commands.entity(entity).remove::<RunningAction>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use crate::{
+        ai::NPCBundle,
+        faction::Faction,
+        stats::{Health, Stat},
+        turn::TurnNumber,
+    };
+
+    use super::{SimulationPlugin, SimulationStats};
+
+    #[test]
+    fn runs_turns_without_any_window() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, SimulationPlugin));
+
+        for _ in 0..100 {
+            app.update();
+        }
+
+        assert!(app.world().resource::<TurnNumber>().0 >= 10);
+    }
+
+    #[test]
+    fn npcs_fight_until_a_knock_out() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, SimulationPlugin));
+        app.world_mut().resource_mut::<SimulationStats>().0.health = Stat::new(5);
+        // Next to the foe spawned at (5, 5)
+        app.world_mut().spawn(NPCBundle::new(
+            "Friend".to_string(),
+            IVec2::new(5, 6),
+            Faction::Friend,
+        ));
+        app.update();
+
+        let total_health = |app: &mut App| {
+            app.world_mut()
+                .query::<&Health>()
+                .iter(app.world())
+                .map(|health| health.value)
+                .sum::<i32>()
+        };
+        let pieces_before = app.world_mut().query::<&Health>().iter(app.world()).count();
+        let health_before = total_health(&mut app);
+        assert_eq!(pieces_before, 3);
+        assert_eq!(health_before, 15);
+
+        for _ in 0..100 {
+            app.update();
+        }
+
+        let pieces_after = app.world_mut().query::<&Health>().iter(app.world()).count();
+        assert!(total_health(&mut app) < health_before);
+        assert_eq!(pieces_after, pieces_before - 1, "Someone was knocked out");
+    }
+}
@@ -1,11 +1,60 @@
-use std::ops::RangeInclusive;
+use std::{ops::RangeInclusive, str::FromStr};
 
-use crate::move_type::MoveCategory;
-use char_animation::anim_key::AnimKey;
+use crate::{
+    faction::Faction,
+    ivec2::OrientationExt,
+    map::{EnvironmentType, TerrainType},
+    move_type::MoveCategory,
+    traps::TrapKind,
+};
+use bevy::math::IVec2;
+use char_animation::{anim_key::AnimKey, orientation::Orientation};
+use spell_data::{SpellCategory, SpellData, SpellHitbox, SpellRays, SpellTerrain, SpellTileEffect};
+
+/// Played instead of the visual effects missing from the spell data
+const DEFAULT_VISUAL_EFFECT: &str = "Hit_Neutral";
+/// The base power of the spell data for 1 damage, until the damage formula uses the stats
+const POWER_PER_DAMAGE: i32 = 10;
 
 #[derive(Debug, Clone)]
 pub struct ProjectileSpell {
-    pub visual_effect: &'static str,
+    pub visual_effect: String,
+    pub rays: ProjectileRays,
+    /// The projectile stops at the first wall instead of flying through it
+    pub stop_at_wall: bool,
+    /// The projectile stops at the first target instead of hitting every target on its way
+    pub stop_at_hit: bool,
+    /// The projectile flies back to the caster once at the end of its path
+    pub boomerang: bool,
+}
+
+/// Directions a projectile is fired in, around the facing orientation of the caster
+///
+/// Same order as the `Rays` of the raw spell data
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectileRays {
+    #[default]
+    One,
+    Three,
+    Five,
+    Four,
+    Eight,
+}
+
+impl ProjectileRays {
+    pub fn directions(&self, orientation: &Orientation) -> Vec<IVec2> {
+        let steps: &[i32] = match self {
+            ProjectileRays::One => &[0],
+            ProjectileRays::Three => &[0, -1, 1],
+            ProjectileRays::Five => &[0, -1, 1, -2, 2],
+            ProjectileRays::Four => &[0, 2, 4, 6],
+            ProjectileRays::Eight => &[0, 1, 2, 3, 4, 5, 6, 7],
+        };
+        steps
+            .iter()
+            .map(|step| orientation.rotate(*step).to_vector())
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -15,22 +64,275 @@ pub enum SpellType {
 
 #[derive(Debug, Clone)]
 pub struct SpellHit {
-    pub visual_effect: &'static str,
+    pub visual_effect: String,
     pub damage: i32,
     pub move_type: MoveCategory,
+    /// Applied to every tile hit, the `OnHitTiles` of the raw data
+    pub tile_effects: Vec<TileEffect>,
+}
+
+/// What a spell does to the tiles it hits
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TileEffect {
+    /// Turn the tiles of one of the `from` types into `to`, e.g. Rock Smash shattering walls
+    ChangeTerrain {
+        from: Vec<TerrainType>,
+        to: TerrainType,
+    },
+    SetTrap(TrapKind),
+    RemoveTrap,
 }
 
 #[derive(Debug, Clone)]
 pub struct SpellCast {
-    pub visual_effect: &'static str,
+    pub visual_effect: String,
     pub animation: AnimKey,
 }
 
+/// Splash damage around the tile where the spell lands
+#[derive(Debug, Clone)]
+pub struct SpellExplosion {
+    /// Distance to the impact tile, diagonals included
+    pub range: i32,
+    pub targets: SpellTargets,
+    /// Spawned on every tile of the explosion
+    pub visual_effect: String,
+}
+
+/// The pieces a spell can hit, relative to the caster, the `TargetAlignments` of the raw data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpellTargets {
+    pub caster: bool,
+    pub allies: bool,
+    pub foes: bool,
+}
+
+impl SpellTargets {
+    pub const FOES: Self = Self {
+        caster: false,
+        allies: false,
+        foes: true,
+    };
+
+    pub fn allows(&self, is_caster: bool, caster_faction: &Faction, faction: &Faction) -> bool {
+        if is_caster {
+            self.caster
+        } else if caster_faction.is_ally(faction) {
+            self.allies
+        } else {
+            self.foes
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Spell {
-    pub name: &'static str,
+    pub name: String,
     pub range: RangeInclusive<i32>,
     pub spell_type: SpellType,
     pub hit: SpellHit,
     pub cast: SpellCast,
+    /// Number of times the spell is fired in a row
+    pub strikes: u32,
+    pub explosion: Option<SpellExplosion>,
+}
+
+impl Spell {
+    pub fn from_data(data: &SpellData) -> Self {
+        let visual_effect = |name: &Option<String>| {
+            name.clone()
+                .unwrap_or_else(|| DEFAULT_VISUAL_EFFECT.to_string())
+        };
+        let hit_visual_effect = visual_effect(&data.hit_visual_effect);
+
+        // Every hitbox is fired as a projectile for now, the melee one only reaches the next tile
+        let (range, projectile, animation) = match &data.hitbox {
+            SpellHitbox::Melee => (
+                1..=1,
+                ProjectileSpell {
+                    visual_effect: hit_visual_effect.clone(),
+                    rays: ProjectileRays::One,
+                    stop_at_wall: true,
+                    stop_at_hit: true,
+                    boomerang: false,
+                },
+                AnimKey::Attack,
+            ),
+            SpellHitbox::Projectile {
+                range,
+                rays,
+                stop_at_wall,
+                stop_at_hit,
+                boomerang,
+                visual_effect: projectile_visual_effect,
+            } => (
+                1..=*range,
+                ProjectileSpell {
+                    visual_effect: visual_effect(projectile_visual_effect),
+                    rays: (*rays).into(),
+                    stop_at_wall: *stop_at_wall,
+                    stop_at_hit: *stop_at_hit,
+                    boomerang: *boomerang,
+                },
+                AnimKey::Shoot,
+            ),
+            SpellHitbox::Caster => (
+                0..=0,
+                ProjectileSpell {
+                    visual_effect: hit_visual_effect.clone(),
+                    rays: ProjectileRays::One,
+                    stop_at_wall: true,
+                    stop_at_hit: true,
+                    boomerang: false,
+                },
+                AnimKey::Charge,
+            ),
+        };
+
+        let damage = match data.power {
+            0 => 0,
+            power => (power / POWER_PER_DAMAGE).max(1),
+        };
+
+        Spell {
+            name: data.name.clone(),
+            range,
+            spell_type: SpellType::Projectile(projectile),
+            hit: SpellHit {
+                visual_effect: hit_visual_effect,
+                damage,
+                move_type: data.category.into(),
+                tile_effects: data
+                    .tile_effects
+                    .iter()
+                    .filter_map(TileEffect::from_data)
+                    .collect(),
+            },
+            cast: SpellCast {
+                visual_effect: visual_effect(&data.cast_visual_effect),
+                animation,
+            },
+            strikes: data.strikes.max(1),
+            explosion: data.explosion.as_ref().map(|explosion| SpellExplosion {
+                range: explosion.range,
+                targets: SpellTargets {
+                    caster: explosion.targets.caster,
+                    allies: explosion.targets.allies,
+                    foes: explosion.targets.foes,
+                },
+                visual_effect: visual_effect(&explosion.visual_effect),
+            }),
+        }
+    }
+}
+
+impl TileEffect {
+    /// `None` for the terrains and traps the map doesn't have
+    pub fn from_data(data: &SpellTileEffect) -> Option<Self> {
+        match data {
+            SpellTileEffect::ClearTerrain(terrains) => {
+                let from = terrains
+                    .iter()
+                    .filter_map(|terrain| match terrain {
+                        SpellTerrain::Wall => Some(TerrainType::Wall),
+                        SpellTerrain::Water => {
+                            Some(TerrainType::Environment(EnvironmentType::Water))
+                        }
+                        SpellTerrain::Lava => Some(TerrainType::Environment(EnvironmentType::Lava)),
+                        SpellTerrain::Abyss | SpellTerrain::Foliage => None,
+                    })
+                    .collect::<Vec<_>>();
+                (!from.is_empty()).then_some(TileEffect::ChangeTerrain {
+                    from,
+                    to: TerrainType::Ground,
+                })
+            }
+            SpellTileEffect::SetTrap(trap_id) => {
+                TrapKind::from_str(trap_id).ok().map(TileEffect::SetTrap)
+            }
+            SpellTileEffect::RemoveTrap => Some(TileEffect::RemoveTrap),
+        }
+    }
+}
+
+impl From<SpellRays> for ProjectileRays {
+    fn from(rays: SpellRays) -> Self {
+        match rays {
+            SpellRays::One => ProjectileRays::One,
+            SpellRays::Three => ProjectileRays::Three,
+            SpellRays::Five => ProjectileRays::Five,
+            SpellRays::Four => ProjectileRays::Four,
+            SpellRays::Eight => ProjectileRays::Eight,
+        }
+    }
+}
+
+impl From<SpellCategory> for MoveCategory {
+    fn from(category: SpellCategory) -> Self {
+        match category {
+            SpellCategory::Physical => MoveCategory::Physical,
+            SpellCategory::Special => MoveCategory::Special,
+            SpellCategory::Status => MoveCategory::Status,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use spell_data::{SpellCategory, SpellData, SpellHitbox, SpellTerrain, SpellTileEffect};
+
+    use crate::{map::TerrainType, traps::TrapKind};
+
+    use super::{Spell, SpellType, TileEffect, DEFAULT_VISUAL_EFFECT};
+
+    #[test]
+    fn melee_data_reaches_the_next_tile() {
+        let spell = Spell::from_data(&SpellData {
+            name: "Double Kick".to_string(),
+            category: SpellCategory::Physical,
+            power: 40,
+            strikes: 2,
+            hitbox: SpellHitbox::Melee,
+            cast_visual_effect: None,
+            hit_visual_effect: Some("Rock_Smash".to_string()),
+            explosion: None,
+            tile_effects: vec![],
+        });
+
+        assert_eq!(spell.range, 1..=1);
+        assert_eq!(spell.strikes, 2);
+        assert_eq!(spell.hit.damage, 4);
+        assert_eq!(spell.hit.visual_effect, "Rock_Smash");
+        assert_eq!(spell.cast.visual_effect, DEFAULT_VISUAL_EFFECT);
+        let SpellType::Projectile(projectile) = spell.spell_type;
+        assert!(projectile.stop_at_hit);
+    }
+
+    #[test]
+    fn tile_effects_keep_the_terrains_of_the_map() {
+        let effects = [
+            SpellTileEffect::ClearTerrain(vec![SpellTerrain::Wall, SpellTerrain::Abyss]),
+            SpellTileEffect::ClearTerrain(vec![SpellTerrain::Foliage]),
+            SpellTileEffect::SetTrap("trap_stealth_rock".to_string()),
+            SpellTileEffect::SetTrap("trap_unknown".to_string()),
+            SpellTileEffect::RemoveTrap,
+        ];
+
+        let effects = effects
+            .iter()
+            .filter_map(TileEffect::from_data)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            effects,
+            vec![
+                TileEffect::ChangeTerrain {
+                    from: vec![TerrainType::Wall],
+                    to: TerrainType::Ground,
+                },
+                TileEffect::SetTrap(TrapKind::Spike),
+                TileEffect::RemoveTrap,
+            ]
+        );
+    }
 }
@@ -1,11 +1,14 @@
 use bevy::prelude::*;
 use bevy_inspector_egui::{inspector_options::ReflectInspectorOptions, InspectorOptions};
 use pokemon_data::PokemonData;
+use serde::{Deserialize, Serialize};
 
 use crate::{pokemons::Pokemon, GameState};
 
 const MAX_STAT: i32 = 255;
 const MAX_HP: i32 = 999;
+pub const DEFAULT_LEVEL: u32 = 5;
+pub const MAX_LEVEL: u32 = 100;
 
 pub struct StatsPlugin;
 
@@ -13,16 +16,22 @@ impl Plugin for StatsPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<Stats>()
             .register_type::<Health>()
+            .register_type::<Level>()
+            .register_type::<Experience>()
             .add_systems(
                 Update,
-                (update_stats_system, add_health_system)
+                (
+                    refresh_reloaded_pokemon_data,
+                    update_stats_system,
+                    add_health_system,
+                )
                     .chain()
                     .run_if(in_state(GameState::Playing)),
             );
     }
 }
 
-#[derive(Debug, Default, Reflect)]
+#[derive(Debug, Default, Clone, Reflect, Serialize, Deserialize)]
 pub struct Stat {
     base: i32,
     bonus: i32,
@@ -38,7 +47,7 @@ impl Stat {
     }
 }
 
-#[derive(Component, Default, Reflect)]
+#[derive(Component, Debug, Default, Clone, Reflect, Serialize, Deserialize)]
 pub struct Health {
     pub value: i32,
     pub max: i32,
@@ -50,7 +59,70 @@ impl Health {
     }
 }
 
-#[derive(Component, Debug, InspectorOptions, Reflect, Default)]
+#[derive(
+    Component, Debug, Clone, Copy, PartialEq, Eq, Reflect, Deref, DerefMut, Serialize, Deserialize,
+)]
+pub struct Level(pub u32);
+
+impl Default for Level {
+    fn default() -> Self {
+        Self(DEFAULT_LEVEL)
+    }
+}
+
+/// Total experience points of the piece, the pieces without it have just reached their level
+#[derive(
+    Component, Debug, Default, Clone, Copy, Reflect, Deref, DerefMut, Serialize, Deserialize,
+)]
+pub struct Experience(pub u32);
+
+/// Total experience needed to reach the level, on the medium fast growth curve
+pub fn experience_for_level(level: u32) -> u32 {
+    level.pow(3)
+}
+
+/// Experience earned by defeating the `target`, from the base experience yield of its form
+pub fn defeat_experience(world: &World, target: Entity) -> u32 {
+    let Some(pokemon) = world.get::<Pokemon>(target) else {
+        return 0;
+    };
+    let Some(form) = world
+        .get::<Handle<PokemonData>>(target)
+        .and_then(|handle| world.resource::<Assets<PokemonData>>().get(handle))
+        .and_then(|data| data.forms.get(pokemon.form_index))
+    else {
+        return 0;
+    };
+    let level = world.get::<Level>(target).copied().unwrap_or_default();
+
+    form.exp_yield.max(0) as u32 * level.0 / 7
+}
+
+/// Add the experience to the piece and raise its level for each threshold it reaches, the
+/// level-ups trigger the evolution checks
+pub fn gain_experience(world: &mut World, entity: Entity, amount: u32) {
+    let Some(level) = world.get::<Level>(entity).copied() else {
+        return;
+    };
+    let experience = world
+        .get::<Experience>(entity)
+        .map_or(experience_for_level(level.0), |experience| experience.0)
+        + amount;
+
+    let mut new_level = level.0;
+    while new_level < MAX_LEVEL && experience >= experience_for_level(new_level + 1) {
+        new_level += 1;
+    }
+
+    let mut entity_mut = world.entity_mut(entity);
+    entity_mut.insert(Experience(experience));
+    if new_level != level.0 {
+        info!("{:?} grew to level {}", entity, new_level);
+        entity_mut.insert(Level(new_level));
+    }
+}
+
+#[derive(Component, Debug, Clone, InspectorOptions, Reflect, Default, Serialize, Deserialize)]
 #[reflect(Component, InspectorOptions)]
 pub struct Stats {
     pub health: Stat,
@@ -61,6 +133,24 @@ pub struct Stats {
     pub speed: Stat,
 }
 
+/// Update the base stats of the pokemons whose data was reloaded
+fn refresh_reloaded_pokemon_data(
+    mut ev_pokemon_data: EventReader<AssetEvent<PokemonData>>,
+    mut query: Query<&mut Handle<PokemonData>>,
+) {
+    for event in ev_pokemon_data.read() {
+        let AssetEvent::Modified { id } = event else {
+            continue;
+        };
+
+        for mut handle in query.iter_mut() {
+            if handle.id() == *id {
+                handle.set_changed();
+            }
+        }
+    }
+}
+
 /// Add or update the base stats of a pokemon based on its pokemon data
 #[allow(clippy::type_complexity)]
 fn update_stats_system(
@@ -98,7 +188,12 @@ fn update_stats_system(
     }
 }
 
-fn add_health_system(mut query: Query<(Entity, &Stats), Added<Stats>>, mut commands: Commands) {
+/// Restored pieces already come with their health
+#[allow(clippy::type_complexity)]
+fn add_health_system(
+    mut query: Query<(Entity, &Stats), (Added<Stats>, Without<Health>)>,
+    mut commands: Commands,
+) {
     for (entity, stats) in query.iter_mut() {
         commands.entity(entity).insert(Health {
             value: stats.health.value(),
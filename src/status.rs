@@ -0,0 +1,86 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    actions::{damage_action::DamageAction, ActionQueue, QueuedAction},
+    move_type::MoveCategory,
+    player::PlayerActionEvent,
+    turn::turn_system,
+    GamePlayingSet,
+};
+
+const POISON_DAMAGE: i32 = 1;
+
+pub struct StatusPlugin;
+
+impl Plugin for StatusPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Poisoned>()
+            .register_type::<Immobilized>()
+            .add_systems(
+                Update,
+                tick_poison
+                    .after(turn_system)
+                    .in_set(GamePlayingSet::TurnLogics),
+            )
+            // Once the turn actions are executed, so the status still applies to this turn
+            .add_systems(Update, tick_immobilized.in_set(GamePlayingSet::LateLogics));
+    }
+}
+
+/// The piece takes damage at the end of each turn
+#[derive(Component, Debug, Clone, Reflect, Serialize, Deserialize)]
+pub struct Poisoned {
+    pub remaining_turns: u32,
+}
+
+/// The piece can't walk until the status expires
+#[derive(Component, Debug, Clone, Reflect, Serialize, Deserialize)]
+pub struct Immobilized {
+    pub remaining_turns: u32,
+}
+
+fn tick_poison(
+    mut query: Query<(Entity, &mut Poisoned)>,
+    mut action_queue: ResMut<ActionQueue>,
+    mut ev_player_action: EventReader<PlayerActionEvent>,
+    mut commands: Commands,
+) {
+    if ev_player_action.read().next().is_none() {
+        return;
+    }
+
+    for (entity, mut poisoned) in query.iter_mut() {
+        action_queue.0.push_back(QueuedAction {
+            entity,
+            performable_actions: vec![Box::new(DamageAction {
+                attacker: entity,
+                target: entity,
+                value: POISON_DAMAGE,
+                move_type: MoveCategory::Physical,
+            })],
+        });
+
+        poisoned.remaining_turns = poisoned.remaining_turns.saturating_sub(1);
+        if poisoned.remaining_turns == 0 {
+            commands.entity(entity).remove::<Poisoned>();
+        }
+    }
+}
+
+fn tick_immobilized(
+    mut query: Query<(Entity, &mut Immobilized)>,
+    mut ev_player_action: EventReader<PlayerActionEvent>,
+    mut commands: Commands,
+) {
+    if ev_player_action.read().next().is_none() {
+        return;
+    }
+
+    for (entity, mut immobilized) in query.iter_mut() {
+        immobilized.remaining_turns = immobilized.remaining_turns.saturating_sub(1);
+        if immobilized.remaining_turns == 0 {
+            commands.entity(entity).remove::<Immobilized>();
+        }
+    }
+}
@@ -0,0 +1,292 @@
+//! Scripted `World` to test the actions without running the whole game
+
+use std::collections::HashMap;
+
+use bevy::{ecs::event::Events, prelude::*};
+use char_animation::orientation::Orientation;
+use pokemon_data::{LocalTexts, PokemonData, PokemonForm, Promotion, TextData};
+
+use crate::{
+    actions::{
+        process_action_queue, Action, ActionBatch, ActionError, ActionExecutedEvent, ActionQueue,
+        ActionRejectedEvent, ProcessingActionEvent, QueuedAction, RunningAction,
+    },
+    faction::Faction,
+    map::{GameMap, Position, TerrainData, TerrainType, Tile},
+    pieces::{Actor, FacingOrientation, Occupier, PieceDeathEvent},
+    pokemons::Pokemon,
+    recruitment::RecruitmentPrompt,
+    rng::GameRng,
+    stats::{Health, Level, Stats},
+    turn::TurnOrder,
+};
+
+/// Maximum number of steps before considering the queue never completes
+const MAX_STEPS: usize = 1000;
+
+pub struct TestWorld {
+    pub world: World,
+    executed: Vec<ActionExecutedEvent>,
+    rejected: Vec<ActionRejectedEvent>,
+}
+
+impl TestWorld {
+    pub fn new(map: GameMap) -> Self {
+        let mut world = World::new();
+        world.insert_resource(map);
+        world.insert_resource(GameRng::from_seed(0));
+        world.init_resource::<ActionQueue>();
+        world.init_resource::<ActionBatch>();
+        world.init_resource::<TurnOrder>();
+        world.init_resource::<RecruitmentPrompt>();
+        world.init_resource::<Assets<PokemonData>>();
+        world.init_resource::<Events<ActionExecutedEvent>>();
+        world.init_resource::<Events<ActionRejectedEvent>>();
+        world.init_resource::<Events<ProcessingActionEvent>>();
+        world.init_resource::<Events<PieceDeathEvent>>();
+
+        let mut test_world = Self {
+            world,
+            executed: vec![],
+            rejected: vec![],
+        };
+        test_world.spawn_tiles();
+        test_world
+    }
+
+    /// A `width` x `height` map of ground, with the given walls
+    pub fn map(width: i32, height: i32, walls: &[IVec2]) -> GameMap {
+        let mut tiles = HashMap::new();
+        for x in 0..width {
+            for y in 0..height {
+                let position = IVec2::new(x, y);
+                let r#type = if walls.contains(&position) {
+                    TerrainType::Wall
+                } else {
+                    TerrainType::Ground
+                };
+                tiles.insert(position, TerrainData { r#type });
+            }
+        }
+
+        GameMap {
+            tiles,
+            tiles_lookup: HashMap::new(),
+        }
+    }
+
+    fn spawn_tiles(&mut self) {
+        let tiles = self.world.resource::<GameMap>().tiles.clone();
+        for (position, tile_data) in tiles {
+            let tile = self.world.spawn((Position(position), Tile(tile_data))).id();
+            self.world
+                .resource_mut::<GameMap>()
+                .associate_entity_to_tile(tile, &position);
+        }
+    }
+
+    /// Spawn an occupier piece with the given health, facing south
+    pub fn spawn_piece(&mut self, position: IVec2, faction: Faction, health: i32) -> Entity {
+        let entity = self
+            .world
+            .spawn((
+                Name::new("Test piece"),
+                Position(position),
+                FacingOrientation(Orientation::South),
+                faction,
+                Level::default(),
+                Stats::default(),
+                Health {
+                    value: health,
+                    max: health,
+                },
+                Occupier,
+                Actor,
+            ))
+            .id();
+        self.world.resource_mut::<TurnOrder>().0.push_back(entity);
+        entity
+    }
+
+    /// Make the piece the given pokemon, with its data
+    pub fn set_pokemon(&mut self, entity: Entity, id: u32, data: PokemonData) {
+        let handle = self.world.resource_mut::<Assets<PokemonData>>().add(data);
+        self.world
+            .entity_mut(entity)
+            .insert((Pokemon { id, form_index: 0 }, handle));
+    }
+
+    /// Pokemon data with a single form and the given promotions
+    pub fn pokemon_data(
+        name: &str,
+        exp_yield: i32,
+        join_rate: i32,
+        promotions: Vec<Promotion>,
+    ) -> PokemonData {
+        let text = |text: &str| TextData {
+            default_text: text.to_string(),
+            local_texts: LocalTexts {
+                ja: None,
+                ko: None,
+                zh_hant: None,
+                fr: None,
+                de: None,
+                es: None,
+                it: None,
+                ja_jp: None,
+                zh_hans: None,
+            },
+        };
+
+        PokemonData {
+            name: text(name),
+            released: true,
+            comment: String::new(),
+            title: text(name),
+            index_num: 0,
+            exp_table: String::new(),
+            skill_group1: String::new(),
+            skill_group2: String::new(),
+            join_rate,
+            promote_from: String::new(),
+            promotions,
+            forms: vec![PokemonForm {
+                released: true,
+                generation: 1,
+                genderless_weight: 0,
+                male_weight: 1,
+                female_weight: 1,
+                base_hp: 45,
+                base_atk: 49,
+                base_def: 49,
+                base_m_atk: 65,
+                base_m_def: 65,
+                base_speed: 45,
+                exp_yield,
+                height: 0.7,
+                weight: 6.9,
+                personalities: vec![],
+                teach_skills: vec![],
+                shared_skills: vec![],
+                secret_skills: vec![],
+                form_name: text(name),
+                temporary: false,
+                promote_form: 0,
+                element1: "normal".to_string(),
+                element2: "none".to_string(),
+                intrinsic1: String::new(),
+                intrinsic2: String::new(),
+                intrinsic3: String::new(),
+                level_skills: vec![],
+            }],
+        }
+    }
+
+    pub fn enqueue(&mut self, entity: Entity, actions: Vec<Box<dyn Action>>) {
+        self.world
+            .resource_mut::<ActionQueue>()
+            .0
+            .push_back(QueuedAction {
+                entity,
+                performable_actions: actions,
+            });
+    }
+
+    /// Step `process_action_queue` until every queued action is resolved, the animations are
+    /// considered instantly finished
+    pub fn run(&mut self) {
+        for _ in 0..MAX_STEPS {
+            process_action_queue(&mut self.world);
+
+            let executed = self
+                .world
+                .resource_mut::<Events<ActionExecutedEvent>>()
+                .drain()
+                .collect::<Vec<_>>();
+            self.executed.extend(executed);
+
+            let rejected = self
+                .world
+                .resource_mut::<Events<ActionRejectedEvent>>()
+                .drain()
+                .collect::<Vec<_>>();
+            self.rejected.extend(rejected);
+
+            let running = self
+                .world
+                .query_filtered::<Entity, With<RunningAction>>()
+                .iter(&self.world)
+                .collect::<Vec<_>>();
+            for entity in running.iter() {
+                self.world.entity_mut(*entity).remove::<RunningAction>();
+            }
+
+            if running.is_empty() && self.world.resource::<ActionQueue>().0.is_empty() {
+                return;
+            }
+        }
+        panic!("The action queue never completed");
+    }
+
+    pub fn health(&self, entity: Entity) -> i32 {
+        self.world
+            .get::<Health>(entity)
+            .expect("The entity has no health")
+            .value
+    }
+
+    pub fn position(&self, entity: Entity) -> IVec2 {
+        self.world
+            .get::<Position>(entity)
+            .expect("The entity has no position")
+            .0
+    }
+
+    pub fn terrain(&self, position: IVec2) -> TerrainType {
+        self.world.resource::<GameMap>().tiles[&position].r#type
+    }
+
+    /// Every executed action of type `A`, in execution order
+    pub fn executed<A: Action + 'static>(&self) -> Vec<&A> {
+        self.executed
+            .iter()
+            .filter_map(|event| event.action.as_any().downcast_ref::<A>())
+            .collect()
+    }
+
+    /// Every error of the rejected actions of type `A`, in rejection order
+    pub fn rejections<A: Action + 'static>(&self) -> Vec<ActionError> {
+        self.rejected
+            .iter()
+            .flat_map(|event| event.rejected.iter())
+            .filter(|(action, _)| action.as_any().is::<A>())
+            .map(|(_, error)| *error)
+            .collect()
+    }
+
+    pub fn assert_health(&self, entity: Entity, expected: i32) {
+        assert_eq!(self.health(entity), expected, "Unexpected health");
+    }
+
+    pub fn assert_position(&self, entity: Entity, expected: IVec2) {
+        assert_eq!(self.position(entity), expected, "Unexpected position");
+    }
+
+    pub fn assert_executed<A: Action + 'static>(&self, expected_count: usize) {
+        assert_eq!(
+            self.executed::<A>().len(),
+            expected_count,
+            "Unexpected number of executed {}",
+            std::any::type_name::<A>()
+        );
+    }
+
+    pub fn assert_rejected<A: Action + 'static>(&self, expected: ActionError) {
+        assert_eq!(
+            self.rejections::<A>(),
+            vec![expected],
+            "Unexpected rejections of {}",
+            std::any::type_name::<A>()
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use bevy::prelude::*;
+use rand::seq::IteratorRandom;
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter, EnumString, IntoEnumIterator};
+
+use crate::{
+    ai::spawn_npcs,
+    map::{spawn_map, GameMap, Position, TerrainType},
+    pieces::{Occupier, Piece, PieceKind},
+    rng::{GameRng, RngStream},
+    GameState,
+};
+
+const TRAPS_PER_FLOOR: usize = 6;
+
+pub struct TrapsPlugin;
+
+impl Plugin for TrapsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Trap>().add_systems(
+            OnEnter(GameState::Playing),
+            // The traps are hidden away from the spawned pieces
+            spawn_traps.after(spawn_map).after(spawn_npcs),
+        );
+    }
+}
+
+/// The trap ids match the `TrapID` used by the raw `SetTrap` events
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    EnumIter,
+    EnumString,
+    Display,
+    Reflect,
+    Serialize,
+    Deserialize,
+)]
+pub enum TrapKind {
+    /// Teleports the piece to a random tile of the floor
+    #[strum(serialize = "trap_warp")]
+    Warp,
+    /// Deals a fixed amount of damage
+    #[strum(serialize = "trap_spikes", serialize = "trap_stealth_rock")]
+    Spike,
+    /// Poisons the piece for a few turns
+    #[strum(serialize = "trap_poison", serialize = "trap_toxic_spikes")]
+    Poison,
+    /// Sticks the piece on the tile for a few turns
+    #[strum(serialize = "trap_sticky")]
+    Sticky,
+    /// Summons foes around the piece
+    #[strum(serialize = "trap_summon")]
+    Summon,
+    /// The piece stumbles and can't move on its next turn
+    #[strum(serialize = "trap_trip")]
+    Trip,
+}
+
+#[derive(Component, Debug, Reflect)]
+pub struct Trap {
+    pub kind: TrapKind,
+}
+
+/// A trap not discovered yet, it is not rendered until it's revealed
+#[derive(Component, Debug)]
+pub struct HiddenTrap;
+
+#[derive(Bundle)]
+pub struct TrapBundle {
+    name: Name,
+    trap: Trap,
+    hidden: HiddenTrap,
+    piece: Piece,
+    position: Position,
+}
+
+impl TrapBundle {
+    pub fn new(kind: TrapKind, position: IVec2) -> Self {
+        Self {
+            name: Name::new(format!("Trap {kind}")),
+            trap: Trap { kind },
+            hidden: HiddenTrap,
+            piece: Piece {
+                kind: PieceKind::Trap,
+            },
+            position: Position(position),
+        }
+    }
+}
+
+/// Hide the traps on free ground tiles of the floor
+pub(crate) fn spawn_traps(
+    map: Res<GameMap>,
+    query_occupier: Query<&Position, With<Occupier>>,
+    mut rng: ResMut<GameRng>,
+    mut commands: Commands,
+) {
+    let rng = rng.stream(RngStream::MapGen);
+
+    let occupied = query_occupier.iter().map(|p| p.0).collect::<Vec<_>>();
+    let mut free_tiles = map
+        .tiles
+        .iter()
+        .filter(|(position, tile)| {
+            tile.r#type == TerrainType::Ground && !occupied.contains(position)
+        })
+        .map(|(position, _)| *position)
+        .collect::<Vec<_>>();
+    // The map iteration order is not deterministic
+    free_tiles.sort_by_key(|position| (position.x, position.y));
+    let free_tiles = free_tiles.into_iter().choose_multiple(rng, TRAPS_PER_FLOOR);
+
+    for position in free_tiles {
+        let Some(kind) = TrapKind::iter().choose(rng) else {
+            continue;
+        };
+        info!("Hide {} trap at {:?}", kind, position);
+        commands.spawn(TrapBundle::new(kind, position));
+    }
+}
@@ -3,7 +3,9 @@ use std::collections::VecDeque;
 use bevy::prelude::*;
 
 use crate::{
-    actions::{ActionQueue, NextActions, ProcessingActionEvent, QueuedAction},
+    actions::{
+        leave_action::LeftFloor, ActionQueue, NextActions, ProcessingActionEvent, QueuedAction,
+    },
     pieces::Actor,
     player::{Player, PlayerActionEvent},
     stats::{Health, Stats},
@@ -15,6 +17,7 @@ pub struct TurnPlugin;
 impl Plugin for TurnPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<TurnOrder>()
+            .init_resource::<TurnNumber>()
             .add_systems(
                 Update,
                 (add_actor_to_queue, turn_system)
@@ -31,8 +34,13 @@ impl Plugin for TurnPlugin {
 #[derive(Default, Resource)]
 pub struct TurnOrder(pub VecDeque<Entity>);
 
+/// Number of turns played since the start of the run
+#[derive(Default, Resource, Debug)]
+pub struct TurnNumber(pub u32);
+
 pub fn turn_system(
     turn_order: ResMut<TurnOrder>,
+    mut turn_number: ResMut<TurnNumber>,
     query_player: Query<Entity, With<Player>>,
     query_next_actions: Query<&NextActions>,
     mut action_queue: ResMut<ActionQueue>,
@@ -42,6 +50,8 @@ pub fn turn_system(
         return;
     };
 
+    turn_number.0 += 1;
+
     info!("--------------- Turn ---------------");
     info!("------------------------------------");
 
@@ -75,7 +85,7 @@ pub fn turn_system(
 
 fn handle_actor_death(
     mut actor_queue: ResMut<TurnOrder>,
-    query_stats: Query<(Entity, &Health)>,
+    query_stats: Query<(Entity, &Health, Has<LeftFloor>)>,
     mut commands: Commands,
     mut ev_processing_action: EventReader<ProcessingActionEvent>,
 ) {
@@ -84,15 +94,15 @@ fn handle_actor_death(
         return;
     }
 
-    for (entity, health) in query_stats.iter() {
-        if !health.is_dead() {
+    for (entity, health, has_left) in query_stats.iter() {
+        if !health.is_dead() && !has_left {
             continue;
         }
-        let death_actor_index = actor_queue.0.iter().position(|e| *e == entity).unwrap();
-
-        info!("Removed {:?} from the actor queue", entity);
-
-        actor_queue.0.remove(death_actor_index);
+        // The pieces that left the floor are already out of the actor queue
+        if let Some(death_actor_index) = actor_queue.0.iter().position(|e| *e == entity) {
+            info!("Removed {:?} from the actor queue", entity);
+            actor_queue.0.remove(death_actor_index);
+        }
 
         commands.entity(entity).despawn_recursive();
     }
@@ -100,6 +110,10 @@ fn handle_actor_death(
 
 fn add_actor_to_queue(query: Query<Entity, Added<Actor>>, mut turn_order: ResMut<TurnOrder>) {
     for entity in query.iter() {
+        // Restored actors are already part of the turn order
+        if turn_order.0.contains(&entity) {
+            continue;
+        }
         info!("Add {:?} to turn order", entity);
         turn_order.0.push_back(entity);
     }
@@ -5,14 +5,12 @@ use std::collections::VecDeque;
 use bevy::prelude::*;
 use bevy_inspector_egui::bevy_egui::{egui, EguiContexts};
 
-use crate::actions::damage_action::DamageAction;
-use crate::actions::death_action::DeathAction;
-use crate::actions::spell_action::SpellAction;
-use crate::actions::walk_action::WalkAction;
-use crate::actions::ActionExecutedEvent;
+use crate::actions::{ActionEvent, ActionExecutedEvent, ActionRejectedEvent};
 use crate::graphics::assets::font_assets::FontAssets;
 use crate::graphics::assets::ui_assets::UIAssets;
 use crate::graphics::ui::{BorderedFrame, UISpriteText, UISpriteTextSection};
+use crate::player::Player;
+use crate::recruitment::RecruitmentAnsweredEvent;
 
 const SCROLL_SPEED: f32 = 15.;
 
@@ -68,52 +66,117 @@ pub(crate) fn gather_logs(
 ) {
     for action_executed in ev_action_executed.read() {
         info!("Gather logs -> {:?}", action_executed.action);
-        let action = action_executed.action.as_any();
         let entity_name = name_query.get(action_executed.entity).unwrap().as_str();
 
-        if let Some(walk_action) = action.downcast_ref::<WalkAction>() {
-            let log_line_sections = vec![
+        let log_line_sections = match &action_executed.event {
+            ActionEvent::Walk(walk_action) => vec![
                 EventLogLineSection::new(entity_name.to_string(), EventLogColor::TeamLeader),
                 EventLogLineSection::new(
                     format!(" walk to {:?}!", walk_action.to),
                     EventLogColor::None,
                 ),
-            ];
-            event_logs.logs.push_back(EventLogLine(log_line_sections));
-            continue;
-        };
-        if let Some(spell_action) = action.downcast_ref::<SpellAction>() {
-            let log_line_sections = vec![
+            ],
+            ActionEvent::Spell(spell_action) => vec![
                 EventLogLineSection::new(entity_name.to_string(), EventLogColor::TeamLeader),
                 EventLogLineSection::new(" used ".to_string(), EventLogColor::None),
-                EventLogLineSection::new(spell_action.spell.name.to_string(), EventLogColor::Spell),
-            ];
-            event_logs.logs.push_back(EventLogLine(log_line_sections));
+                EventLogLineSection::new(spell_action.spell.name.clone(), EventLogColor::Spell),
+            ],
+            ActionEvent::SpellStrike(strike_action) if strike_action.spell.strikes > 1 => {
+                vec![
+                    EventLogLineSection::new(entity_name.to_string(), EventLogColor::TeamLeader),
+                    EventLogLineSection::new(" strikes ".to_string(), EventLogColor::None),
+                    EventLogLineSection::new(
+                        format!("{}/{}", strike_action.strike, strike_action.spell.strikes),
+                        EventLogColor::Damage,
+                    ),
+                    EventLogLineSection::new("!".to_string(), EventLogColor::None),
+                ]
+            }
+            ActionEvent::Damage(damage_action) => {
+                let entity_name = name_query.get(damage_action.target).unwrap().as_str();
+                vec![
+                    EventLogLineSection::new(entity_name.to_string(), EventLogColor::Foe),
+                    EventLogLineSection::new(" took ".to_string(), EventLogColor::None),
+                    EventLogLineSection::new(
+                        damage_action.value.to_string(),
+                        EventLogColor::Damage,
+                    ),
+                    EventLogLineSection::new(" damage!".to_string(), EventLogColor::None),
+                ]
+            }
+            ActionEvent::Death(death_action) => {
+                let entity_name = name_query.get(death_action.target).unwrap().as_str();
+                vec![
+                    EventLogLineSection::new(entity_name.to_string(), EventLogColor::Foe),
+                    EventLogLineSection::new(" was defeated!".to_string(), EventLogColor::None),
+                ]
+            }
+            ActionEvent::Recruit(recruit_action) => {
+                let entity_name = name_query.get(recruit_action.target).unwrap().as_str();
+                vec![
+                    EventLogLineSection::new(entity_name.to_string(), EventLogColor::Foe),
+                    EventLogLineSection::new(
+                        " asks to join your team!".to_string(),
+                        EventLogColor::None,
+                    ),
+                ]
+            }
+            ActionEvent::Evolve(_) => vec![
+                EventLogLineSection::new(entity_name.to_string(), EventLogColor::TeamLeader),
+                EventLogLineSection::new(" is evolving!".to_string(), EventLogColor::None),
+            ],
+            _ => continue,
+        };
+        event_logs.logs.push_back(EventLogLine(log_line_sections));
+    }
+}
+
+/// Tell the player why none of its actions could be done
+pub(crate) fn gather_rejection_logs(
+    mut ev_action_rejected: EventReader<ActionRejectedEvent>,
+    name_query: Query<&Name, With<Player>>,
+    mut event_logs: ResMut<EventLogs>,
+) {
+    for action_rejected in ev_action_rejected.read() {
+        if action_rejected.executed.is_some() {
             continue;
         }
-        if let Some(damage_action) = action.downcast_ref::<DamageAction>() {
-            let entity_name = name_query.get(damage_action.target).unwrap().as_str();
-
-            let log_line_sections = vec![
-                EventLogLineSection::new(entity_name.to_string(), EventLogColor::Foe),
-                EventLogLineSection::new(" took ".to_string(), EventLogColor::None),
-                EventLogLineSection::new(damage_action.value.to_string(), EventLogColor::Damage),
-                EventLogLineSection::new(" damage!".to_string(), EventLogColor::None),
-            ];
-            event_logs.logs.push_back(EventLogLine(log_line_sections));
+        let Ok(entity_name) = name_query.get(action_rejected.entity) else {
             continue;
-        }
+        };
+        let Some((_, error)) = action_rejected.rejected.first() else {
+            continue;
+        };
 
-        if let Some(death_action) = action.downcast_ref::<DeathAction>() {
-            let entity_name = name_query.get(death_action.target).unwrap().as_str();
+        let log_line_sections = vec![
+            EventLogLineSection::new(entity_name.to_string(), EventLogColor::TeamLeader),
+            EventLogLineSection::new(format!(" can't do that: {}!", error), EventLogColor::None),
+        ];
+        event_logs.logs.push_back(EventLogLine(log_line_sections));
+    }
+}
 
-            let log_line_sections = vec![
-                EventLogLineSection::new(entity_name.to_string(), EventLogColor::Foe),
-                EventLogLineSection::new(" was defeated!".to_string(), EventLogColor::None),
-            ];
-            event_logs.logs.push_back(EventLogLine(log_line_sections));
+pub(crate) fn gather_recruitment_logs(
+    mut ev_recruitment_answered: EventReader<RecruitmentAnsweredEvent>,
+    name_query: Query<&Name>,
+    mut event_logs: ResMut<EventLogs>,
+) {
+    for answer in ev_recruitment_answered.read() {
+        let Ok(entity_name) = name_query.get(answer.recruit) else {
             continue;
-        }
+        };
+
+        let (color, text) = if answer.accepted {
+            (EventLogColor::TeamMember, " joined your team!")
+        } else {
+            (EventLogColor::Foe, " went away...")
+        };
+
+        let log_line_sections = vec![
+            EventLogLineSection::new(entity_name.to_string(), color),
+            EventLogLineSection::new(text.to_string(), EventLogColor::None),
+        ];
+        event_logs.logs.push_back(EventLogLine(log_line_sections));
     }
 }
 
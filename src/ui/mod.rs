@@ -1,10 +1,14 @@
 mod event_logger;
+mod recruitment_prompt;
 
 use bevy::prelude::*;
 
 use crate::GameState;
 
-use self::event_logger::{event_logger_ui, gather_logs, EventLogs};
+use self::event_logger::{
+    event_logger_ui, gather_logs, gather_recruitment_logs, gather_rejection_logs, EventLogs,
+};
+use self::recruitment_prompt::recruitment_prompt_ui;
 
 pub struct UIPlugin;
 
@@ -12,7 +16,14 @@ impl Plugin for UIPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<EventLogs>().add_systems(
             Update,
-            (gather_logs, event_logger_ui).run_if(in_state(GameState::Playing)),
+            (
+                gather_logs,
+                gather_recruitment_logs,
+                gather_rejection_logs,
+                event_logger_ui,
+                recruitment_prompt_ui,
+            )
+                .run_if(in_state(GameState::Playing)),
         );
 
         #[cfg(debug_assertions)]
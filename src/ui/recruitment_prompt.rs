@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+use bevy_inspector_egui::bevy_egui::{egui, EguiContexts};
+
+use crate::graphics::assets::font_assets::FontAssets;
+use crate::graphics::assets::ui_assets::UIAssets;
+use crate::graphics::ui::{BorderedFrame, UISpriteText, UISpriteTextSection};
+use crate::recruitment::RecruitmentPrompt;
+
+use super::event_logger::EventLogColor;
+
+pub(crate) fn recruitment_prompt_ui(
+    mut ctx: EguiContexts,
+    prompt: Res<RecruitmentPrompt>,
+    name_query: Query<&Name>,
+    font_assets: Res<FontAssets>,
+    ui_assets: Res<UIAssets>,
+) {
+    let Some(recruit) = prompt.recruit else {
+        return;
+    };
+    let recruit_name = name_query.get(recruit).map_or("???", |name| name.as_str());
+
+    let ctx = ctx.ctx_mut();
+
+    egui::Area::new(egui::Id::new("recruitment_prompt"))
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(ctx, |ui| {
+            BorderedFrame::new(&ui_assets.panel_blue)
+                .background(&ui_assets.transparent_panel_bg)
+                .padding(UiRect::axes(Val::Px(12.), Val::Px(10.)))
+                .show(ui, |ui| {
+                    ui.spacing_mut().item_spacing.y = 0.;
+
+                    UISpriteText::from_sections([
+                        UISpriteTextSection::new(recruit_name, &font_assets.text)
+                            .with_color(EventLogColor::Foe.to_color32()),
+                        UISpriteTextSection::new(" asks to join your team!", &font_assets.text),
+                    ])
+                    .show(ui);
+                    UISpriteText::from_section("Enter: Accept   Esc: Decline", &font_assets.text)
+                        .show(ui);
+                });
+        });
+}
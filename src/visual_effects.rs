@@ -9,7 +9,7 @@ impl Plugin for VisualEffectsPlugin {
 
 #[derive(Component, Debug)]
 pub struct VisualEffect {
-    pub name: &'static str,
+    pub name: String,
     pub is_loop: bool,
 }
 